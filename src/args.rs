@@ -0,0 +1,506 @@
+use std::fmt;
+
+use crate::output::RAW_FIELDS;
+
+// more decimals than the api reports would only show float noise
+const MAX_PRECISION: usize = 3;
+
+// lowest refresh interval accepted by --watch, keeps api quota in check
+pub const MIN_WATCH_SECS: u64 = 60;
+
+// shown for --help, the --raw field names are appended from RAW_FIELDS
+const USAGE: &str = "Usage: cliweather [SUBCOMMAND] [OPTIONS]
+
+Subcommands:
+  now                 current weather (default)
+  forecast            daily forecast
+  search <query>      find locations matching query
+  astro               sunrise, sunset and moon times
+  recents             list recently queried locations
+  login               verify an api key and save it to the OS keyring with the
+                      keyring feature, otherwise in plaintext to the config file
+
+Options:
+  --location <city>   location to query, may be repeated
+  --api-key <key>     WeatherAPI key, overrides keyring, config and API_KEY
+  --format <format>   text, json, table, csv or card (table, csv and card: now only)
+  --units <units>     metric or imperial
+  --locale <locale>   en-US or eu number formatting
+  --precision <n>     decimals shown for temperatures, 0 to 3
+  --watch <secs>      refresh every secs seconds, at least 60 (now only)
+  --raw <field>       print a single field without decoration (now only)
+  --icon              print only the condition glyph (now only)
+  --icon-set <set>    emoji or nerdfont, used with --icon
+  --recent <n>        query entry n of `recents` instead of --location (now only)
+  --days <n>          number of forecast days, 1 to 14 (forecast only)
+  --date <date>       YYYY-MM-DD, defaults to today at the location (astro only)
+  -h, --help          print this help";
+
+// usage text with the --raw field names
+pub fn usage() -> String {
+    format!(
+        "{}\n\nFields for --raw:\n  {}",
+        USAGE,
+        RAW_FIELDS.join(", ")
+    )
+}
+
+// output formats supported by the cli
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+    Table,
+    Csv,
+    Card,
+}
+
+impl std::str::FromStr for Format {
+    type Err = ArgsError;
+
+    fn from_str(value: &str) -> Result<Format, ArgsError> {
+        match value {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "table" => Ok(Format::Table),
+            "csv" => Ok(Format::Csv),
+            "card" => Ok(Format::Card),
+            _ => Err(ArgsError(format!(
+                "invalid value '{}' for --format, expected one of: text, json, table, csv, card",
+                value
+            ))),
+        }
+    }
+}
+
+// unit systems supported by the cli for human readable output
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Units {
+    Metric,
+    Imperial,
+}
+
+impl std::str::FromStr for Units {
+    type Err = ArgsError;
+
+    fn from_str(value: &str) -> Result<Units, ArgsError> {
+        match value {
+            "metric" => Ok(Units::Metric),
+            "imperial" => Ok(Units::Imperial),
+            _ => Err(ArgsError(format!(
+                "invalid value '{}' for --units, expected one of: metric, imperial",
+                value
+            ))),
+        }
+    }
+}
+
+// number formatting conventions supported by the cli
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Locale {
+    EnUs,
+    European,
+}
+
+impl Locale {
+    // unit system used when neither --units nor the config pick one
+    pub fn units(&self) -> Units {
+        match self {
+            Locale::EnUs => Units::Imperial,
+            Locale::European => Units::Metric,
+        }
+    }
+}
+
+impl std::str::FromStr for Locale {
+    type Err = ArgsError;
+
+    fn from_str(value: &str) -> Result<Locale, ArgsError> {
+        match value {
+            "en-US" => Ok(Locale::EnUs),
+            "eu" => Ok(Locale::European),
+            _ => Err(ArgsError(format!(
+                "invalid value '{}' for --locale, expected one of: en-US, eu",
+                value
+            ))),
+        }
+    }
+}
+
+// glyph sets supported by --icon
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IconSet {
+    Emoji,
+    NerdFont,
+}
+
+impl std::str::FromStr for IconSet {
+    type Err = ArgsError;
+
+    fn from_str(value: &str) -> Result<IconSet, ArgsError> {
+        match value {
+            "emoji" => Ok(IconSet::Emoji),
+            "nerdfont" => Ok(IconSet::NerdFont),
+            _ => Err(ArgsError(format!(
+                "invalid value '{}' for --icon-set, expected one of: emoji, nerdfont",
+                value
+            ))),
+        }
+    }
+}
+
+// subcommands, running without one fetches the current weather
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Command {
+    Now,
+    Forecast,
+    Search,
+    Astro,
+    Recents,
+    Login,
+}
+
+impl Command {
+    // name as typed on the command line
+    pub fn name(&self) -> &'static str {
+        match self {
+            Command::Now => "now",
+            Command::Forecast => "forecast",
+            Command::Search => "search",
+            Command::Astro => "astro",
+            Command::Recents => "recents",
+            Command::Login => "login",
+        }
+    }
+
+    // subcommand for a positional argument, if it names one
+    fn from_name(name: &str) -> Option<Command> {
+        [
+            Command::Now,
+            Command::Forecast,
+            Command::Search,
+            Command::Astro,
+            Command::Recents,
+            Command::Login,
+        ]
+        .into_iter()
+        .find(|command| command.name() == name)
+    }
+}
+
+/// ArgsError represents an invalid or incomplete command line.
+#[derive(Debug)]
+pub struct ArgsError(String);
+
+impl fmt::Display for ArgsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for ArgsError {}
+
+/// Args holds the options passed to the cli on the command line.
+#[derive(Debug)]
+pub struct Args {
+    pub command: Command,
+    // print usage instead of running, other args are ignored
+    pub help: bool,
+    pub format: Format,
+    pub units: Option<Units>,
+    pub locale: Option<Locale>,
+    pub api_key: Option<String>,
+    pub locations: Vec<String>,
+    pub watch: Option<u64>,
+    pub raw: Option<String>,
+    pub precision: Option<usize>,
+    // print only the condition glyph, overrides format
+    pub icon: bool,
+    pub icon_set: Option<IconSet>,
+    // index into the recents list, used in place of --location
+    pub recent: Option<usize>,
+    // forecast only
+    pub days: Option<u8>,
+    // astro only
+    pub date: Option<String>,
+    // search only
+    pub query: Option<String>,
+}
+
+impl Args {
+    // parse args passed to the current process
+    pub fn parse() -> Result<Args, ArgsError> {
+        Args::parse_from(std::env::args().skip(1))
+    }
+
+    // parse args from iterator, excluding the program name
+    pub fn parse_from<I: IntoIterator<Item = String>>(args: I) -> Result<Args, ArgsError> {
+        let mut command: Option<Command> = None;
+        let mut parsed: Args = Args {
+            command: Command::Now,
+            help: false,
+            format: Format::Text,
+            units: None,
+            locale: None,
+            api_key: None,
+            locations: Vec::new(),
+            watch: None,
+            raw: None,
+            precision: None,
+            icon: false,
+            icon_set: None,
+            recent: None,
+            days: None,
+            date: None,
+            query: None,
+        };
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            // support both `--flag value` and `--flag=value`
+            let (flag, inline_value): (String, Option<String>) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => {
+                    (flag.to_string(), Some(value.to_string()))
+                }
+                _ => (arg, None),
+            };
+            let mut value = |flag: &str| -> Result<String, ArgsError> {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| ArgsError(format!("missing value for {}", flag)))
+            };
+
+            match flag.as_str() {
+                "-h" | "--help" => {
+                    parsed.help = true;
+                    return Ok(parsed);
+                }
+                "--format" => parsed.format = value("--format")?.parse()?,
+                "--units" => parsed.units = Some(value("--units")?.parse()?),
+                "--locale" => parsed.locale = Some(value("--locale")?.parse()?),
+                "--api-key" => parsed.api_key = Some(value("--api-key")?),
+                "--location" => parsed.locations.push(value("--location")?),
+                "--raw" => parsed.raw = Some(value("--raw")?),
+                "--precision" => parsed.precision = Some(parse_precision(&value("--precision")?)?),
+                "--watch" => parsed.watch = Some(parse_watch(&value("--watch")?)?),
+                "--icon" if inline_value.is_none() => parsed.icon = true,
+                "--icon-set" => parsed.icon_set = Some(value("--icon-set")?.parse()?),
+                "--recent" => parsed.recent = Some(parse_recent(&value("--recent")?)?),
+                "--days" => parsed.days = Some(parse_days(&value("--days")?)?),
+                "--date" => parsed.date = Some(value("--date")?),
+                _ if flag.starts_with('-') => {
+                    return Err(ArgsError(format!("unexpected argument '{}'", flag)))
+                }
+                _ => match command {
+                    // only the first positional argument picks the subcommand
+                    None => match Command::from_name(&flag) {
+                        Some(subcommand) => command = Some(subcommand),
+                        None => return Err(ArgsError(format!("unknown subcommand '{}'", flag))),
+                    },
+                    Some(Command::Search) if parsed.query.is_none() => parsed.query = Some(flag),
+                    Some(_) => return Err(ArgsError(format!("unexpected argument '{}'", flag))),
+                },
+            }
+        }
+
+        parsed.command = command.unwrap_or(Command::Now);
+        parsed.validate()?;
+
+        Ok(parsed)
+    }
+
+    // reject flags passed to a subcommand that doesn't use them
+    fn validate(&self) -> Result<(), ArgsError> {
+        let only = |flag: &str, present: bool, expected: Command| -> Result<(), ArgsError> {
+            if present && self.command != expected {
+                return Err(ArgsError(format!(
+                    "{} is only supported by the {} subcommand",
+                    flag,
+                    expected.name()
+                )));
+            }
+
+            Ok(())
+        };
+
+        only("--days", self.days.is_some(), Command::Forecast)?;
+        only("--date", self.date.is_some(), Command::Astro)?;
+        only("--watch", self.watch.is_some(), Command::Now)?;
+        only("--raw", self.raw.is_some(), Command::Now)?;
+        only("--recent", self.recent.is_some(), Command::Now)?;
+        only("--icon", self.icon, Command::Now)?;
+
+        if self.icon && self.raw.is_some() {
+            return Err(ArgsError("--icon can't be combined with --raw".to_string()));
+        }
+
+        if self.icon_set.is_some() && !self.icon {
+            return Err(ArgsError("--icon-set requires --icon".to_string()));
+        }
+        only(
+            "--format table, csv and card",
+            !matches!(self.format, Format::Text | Format::Json),
+            Command::Now,
+        )?;
+
+        if self.recent.is_some() && !self.locations.is_empty() {
+            return Err(ArgsError(
+                "--recent can't be combined with --location".to_string(),
+            ));
+        }
+
+        if self.command == Command::Search && self.query.is_none() {
+            return Err(ArgsError(
+                "missing search query, e.g. `search London`".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+// parse --watch interval, rejecting anything below the minimum
+fn parse_watch(value: &str) -> Result<u64, ArgsError> {
+    let secs: u64 = value.parse().map_err(|_| {
+        ArgsError(format!(
+            "invalid value '{}' for --watch, expected a number of seconds",
+            value
+        ))
+    })?;
+
+    if secs < MIN_WATCH_SECS {
+        return Err(ArgsError(format!(
+            "--watch interval must be at least {} seconds",
+            MIN_WATCH_SECS
+        )));
+    }
+
+    Ok(secs)
+}
+
+// parse --days, the api bounds are checked by the library
+fn parse_days(value: &str) -> Result<u8, ArgsError> {
+    value.parse().map_err(|_| {
+        ArgsError(format!(
+            "invalid value '{}' for --days, expected a number of days",
+            value
+        ))
+    })
+}
+
+// parse --recent, position in the recents list starting at 0
+fn parse_recent(value: &str) -> Result<usize, ArgsError> {
+    value.parse().map_err(|_| {
+        ArgsError(format!(
+            "invalid value '{}' for --recent, expected a position from `recents`",
+            value
+        ))
+    })
+}
+
+// parse --precision, number of decimals shown for temperatures
+fn parse_precision(value: &str) -> Result<usize, ArgsError> {
+    match value.parse() {
+        Ok(precision) if precision <= MAX_PRECISION => Ok(precision),
+        _ => Err(ArgsError(format!(
+            "invalid value '{}' for --precision, expected a number between 0 and {}",
+            value, MAX_PRECISION
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, ArgsError> {
+        Args::parse_from(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn defaults_to_text_format() {
+        let args: Args = parse(&[]).unwrap();
+
+        assert_eq!(args.format, Format::Text);
+        assert!(args.locations.is_empty());
+    }
+
+    #[test]
+    fn accepts_separate_and_inline_values() {
+        let args: Args =
+            parse(&["--format", "json", "--location=Paris", "--location", "Rome"]).unwrap();
+
+        assert_eq!(args.format, Format::Json);
+        assert_eq!(args.locations, ["Paris", "Rome"]);
+    }
+
+    #[test]
+    fn rejects_unknown_format() {
+        let err: ArgsError = parse(&["--format", "xml"]).unwrap_err();
+
+        assert!(err
+            .to_string()
+            .starts_with("invalid value 'xml' for --format"));
+    }
+
+    #[test]
+    fn rejects_missing_value() {
+        let err: ArgsError = parse(&["--format"]).unwrap_err();
+
+        assert_eq!(err.to_string(), "missing value for --format");
+    }
+
+    #[test]
+    fn rejects_unknown_flag() {
+        let err: ArgsError = parse(&["--verbose"]).unwrap_err();
+
+        assert_eq!(err.to_string(), "unexpected argument '--verbose'");
+    }
+
+    #[test]
+    fn parses_subcommands() {
+        assert_eq!(parse(&[]).unwrap().command, Command::Now);
+        assert_eq!(parse(&["astro"]).unwrap().command, Command::Astro);
+
+        let args: Args = parse(&["forecast", "--days", "5"]).unwrap();
+        assert_eq!(args.command, Command::Forecast);
+        assert_eq!(args.days, Some(5));
+
+        let args: Args = parse(&["search", "Lond"]).unwrap();
+        assert_eq!(args.command, Command::Search);
+        assert_eq!(args.query.as_deref(), Some("Lond"));
+    }
+
+    #[test]
+    fn rejects_unknown_subcommand() {
+        let err: ArgsError = parse(&["weather"]).unwrap_err();
+
+        assert_eq!(err.to_string(), "unknown subcommand 'weather'");
+    }
+
+    #[test]
+    fn search_needs_a_single_query() {
+        assert!(parse(&["search"]).is_err());
+        assert!(parse(&["search", "London", "Paris"]).is_err());
+    }
+
+    #[test]
+    fn rejects_flags_of_other_subcommands() {
+        let err: ArgsError = parse(&["now", "--days", "3"]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "--days is only supported by the forecast subcommand"
+        );
+
+        assert!(parse(&["forecast", "--date", "2024-01-15"]).is_err());
+        assert!(parse(&["astro", "--format", "table"]).is_err());
+        assert!(parse(&["search", "London", "--watch", "60"]).is_err());
+    }
+
+    #[test]
+    fn help_skips_validation() {
+        assert!(parse(&["--days", "3", "--help"]).unwrap().help);
+        assert!(parse(&["-h"]).unwrap().help);
+    }
+}
@@ -0,0 +1,33 @@
+use std::io::IsTerminal;
+
+// ansi sgr codes used by the cli
+pub const BOLD: &str = "1";
+pub const RED: &str = "31";
+pub const GREEN: &str = "32";
+pub const YELLOW: &str = "33";
+pub const BLUE: &str = "34";
+
+// colors are disabled by a non-empty NO_COLOR or when stdout isn't a tty
+pub fn enabled() -> bool {
+    let no_color: bool = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+
+    !no_color && std::io::stdout().is_terminal()
+}
+
+// wrap text in the given ansi code when colors are enabled
+pub fn paint(text: &str, code: &str, enabled: bool) -> String {
+    if enabled {
+        format!("\x1b[{}m{}\x1b[0m", code, text)
+    } else {
+        text.to_string()
+    }
+}
+
+// tint by temperature range, always judged in celsius
+pub fn temperature(temp_c: f32) -> &'static str {
+    match temp_c {
+        temp_c if temp_c < 10.0 => BLUE,
+        temp_c if temp_c <= 25.0 => GREEN,
+        _ => RED,
+    }
+}
@@ -0,0 +1,366 @@
+use std::fmt;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::args::Units;
+
+// template written to the config path on first run
+const TEMPLATE: &str = r#"# weathercli configuration
+#
+# values here override the API_KEY and LOCATION environment variables,
+# and are overridden by command line flags
+
+# api_key = "your-weatherapi-key"
+# default_location = "London"
+# units = "metric"
+"#;
+
+/// ConfigError represents a config file that could not be read or parsed.
+#[derive(Debug)]
+pub struct ConfigError(String);
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Config holds defaults read from `~/.config/weathercli/config.toml`.
+#[derive(Debug, Default)]
+pub struct Config {
+    pub api_key: Option<String>,
+    pub default_location: Option<String>,
+    pub units: Option<Units>,
+}
+
+impl Config {
+    // location of config file, honouring XDG_CONFIG_HOME
+    pub fn path() -> Option<PathBuf> {
+        let config_dir: PathBuf = match std::env::var_os("XDG_CONFIG_HOME") {
+            Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+            _ => PathBuf::from(std::env::var_os("HOME")?).join(".config"),
+        };
+
+        Some(config_dir.join("weathercli").join("config.toml"))
+    }
+
+    // load config file, writing the template if it doesn't exist yet
+    pub fn load() -> Result<Config, ConfigError> {
+        let path: PathBuf = match Config::path() {
+            Some(path) => path,
+            None => return Ok(Config::default()),
+        };
+
+        if !path.exists() {
+            // a missing or read-only config dir shouldn't stop the cli
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent).ok();
+            }
+            std::fs::write(&path, TEMPLATE).ok();
+
+            return Ok(Config::default());
+        }
+
+        let contents: String = std::fs::read_to_string(&path)
+            .map_err(|err| ConfigError(format!("failed reading {}: {}", path.display(), err)))?;
+
+        Config::parse(&contents)
+            .map_err(|err| ConfigError(format!("failed parsing {}: {}", path.display(), err)))
+    }
+
+    // save api key to config file in plaintext, replacing any existing key
+    // the file is made readable by the owner only before the key is written
+    pub fn store_api_key(api_key: &str) -> Result<PathBuf, ConfigError> {
+        let path: PathBuf = Config::path()
+            .ok_or_else(|| ConfigError("no config directory, HOME is not set".to_string()))?;
+
+        Config::store_api_key_at(&path, api_key)?;
+
+        Ok(path)
+    }
+
+    // save api key to the config file at path
+    fn store_api_key_at(path: &Path, api_key: &str) -> Result<(), ConfigError> {
+        let contents: String =
+            std::fs::read_to_string(path).unwrap_or_else(|_| TEMPLATE.to_string());
+        let line: String = format!("api_key = {}", quote_string(api_key));
+
+        let mut replaced: bool = false;
+        let mut lines: Vec<String> = Vec::new();
+        for existing in contents.lines() {
+            let key: &str = existing.trim().split('=').next().unwrap_or_default().trim();
+
+            if key == "api_key" && !replaced {
+                lines.push(line.clone());
+                replaced = true;
+            } else if key != "api_key" {
+                lines.push(existing.to_string());
+            }
+        }
+        if !replaced {
+            lines.push(line);
+        }
+
+        let write_err = |err: std::io::Error| {
+            ConfigError(format!("failed writing {}: {}", path.display(), err))
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(write_err)?;
+        }
+
+        let mut options: std::fs::OpenOptions = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+
+            options.mode(0o600);
+        }
+        let mut file: std::fs::File = options.open(path).map_err(write_err)?;
+
+        // mode only applies to new files, tighten an existing one too
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            file.set_permissions(std::fs::Permissions::from_mode(0o600))
+                .map_err(write_err)?;
+        }
+
+        file.write_all((lines.join("\n") + "\n").as_bytes())
+            .map_err(write_err)?;
+
+        Ok(())
+    }
+
+    // parse flat `key = "value"` pairs, the subset of toml used by the config
+    pub fn parse(contents: &str) -> Result<Config, ConfigError> {
+        let mut config: Config = Config::default();
+
+        for (index, line) in contents.lines().enumerate() {
+            let line: &str = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value): (&str, &str) = line
+                .split_once('=')
+                .ok_or_else(|| ConfigError(format!("line {}: expected key = value", index + 1)))?;
+            let value: String = parse_string(value.trim()).ok_or_else(|| {
+                ConfigError(format!("line {}: expected quoted string", index + 1))
+            })?;
+
+            match key.trim() {
+                "api_key" => config.api_key = Some(value),
+                "default_location" => config.default_location = Some(value),
+                "units" => {
+                    config.units = Some(
+                        value
+                            .parse()
+                            .map_err(|err| ConfigError(format!("line {}: {}", index + 1, err)))?,
+                    )
+                }
+                // unknown keys are ignored
+                _ => {}
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+// first api key found, in order: flag > keyring > config > env var
+// keyring + env var are only consulted when the sources before them are empty
+pub fn resolve_api_key<E>(
+    flag: Option<String>,
+    keyring: impl FnOnce() -> Option<String>,
+    config: Option<String>,
+    env_var: impl FnOnce() -> Result<Option<String>, E>,
+) -> Result<Option<String>, E> {
+    match flag.or_else(keyring).or(config) {
+        Some(api_key) => Ok(Some(api_key)),
+        None => env_var(),
+    }
+}
+
+// parse a basic toml string, allowing a trailing comment
+fn parse_string(value: &str) -> Option<String> {
+    let mut chars = value.strip_prefix('"')?.chars();
+    let mut parsed: String = String::new();
+
+    loop {
+        match chars.next()? {
+            '"' => break,
+            '\\' => match chars.next()? {
+                '"' => parsed.push('"'),
+                '\\' => parsed.push('\\'),
+                'n' => parsed.push('\n'),
+                't' => parsed.push('\t'),
+                _ => return None,
+            },
+            c => parsed.push(c),
+        }
+    }
+
+    let rest: &str = chars.as_str().trim();
+    if rest.is_empty() || rest.starts_with('#') {
+        Some(parsed)
+    } else {
+        None
+    }
+}
+
+// quote value as a basic toml string
+fn quote_string(value: &str) -> String {
+    let mut quoted: String = String::from('"');
+
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            c => quoted.push(c),
+        }
+    }
+
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_escapes() {
+        let config: Config = Config::parse(r#"api_key = "a\"b\\c\td""#).unwrap();
+
+        assert_eq!(config.api_key.as_deref(), Some("a\"b\\c\td"));
+    }
+
+    #[test]
+    fn allows_trailing_comment() {
+        let config: Config = Config::parse("default_location = \"London\" # home").unwrap();
+
+        assert_eq!(config.default_location.as_deref(), Some("London"));
+    }
+
+    #[test]
+    fn rejects_single_quoted_strings() {
+        let err: ConfigError = Config::parse("api_key = 'abc'").unwrap_err();
+
+        assert_eq!(err.to_string(), "line 1: expected quoted string");
+    }
+
+    #[test]
+    fn rejects_text_after_string() {
+        assert!(Config::parse("api_key = \"abc\" extra").is_err());
+    }
+
+    #[test]
+    fn ignores_unknown_keys_and_comments() {
+        let config: Config =
+            Config::parse("# comment\n\ntheme = \"dark\"\nunits = \"imperial\"").unwrap();
+
+        assert_eq!(config.units, Some(Units::Imperial));
+        assert_eq!(config.api_key, None);
+    }
+
+    #[test]
+    fn rejects_bad_units() {
+        let err: ConfigError = Config::parse("\nunits = \"kelvin\"").unwrap_err();
+
+        assert!(err
+            .to_string()
+            .starts_with("line 2: invalid value 'kelvin'"));
+    }
+
+    #[test]
+    fn quoted_strings_parse_back() {
+        let value: &str = "a\"b\\c\nd\te";
+
+        assert_eq!(parse_string(&quote_string(value)).as_deref(), Some(value));
+    }
+
+    #[test]
+    fn store_api_key_replaces_existing_key() {
+        let dir: PathBuf =
+            std::env::temp_dir().join(format!("weathercli-config-{}", std::process::id()));
+        let path: PathBuf = dir.join("config.toml");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            &path,
+            "api_key = \"old\"\ndefault_location = \"Paris\"\napi_key = \"older\"\n",
+        )
+        .unwrap();
+
+        Config::store_api_key_at(&path, "new\"key").unwrap();
+
+        let contents: String = std::fs::read_to_string(&path).unwrap();
+        let config: Config = Config::parse(&contents).unwrap();
+        assert_eq!(config.api_key.as_deref(), Some("new\"key"));
+        assert_eq!(config.default_location.as_deref(), Some("Paris"));
+        assert_eq!(contents.matches("api_key").count(), 1);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            let mode: u32 = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    // source that must not be consulted once an earlier one had a key
+    fn unreachable<T>() -> T {
+        panic!("later api key source was consulted")
+    }
+
+    #[test]
+    fn flag_key_wins_without_consulting_other_sources() {
+        let api_key: Result<Option<String>, ()> = resolve_api_key(
+            Some("flag".to_string()),
+            unreachable,
+            Some("config".to_string()),
+            unreachable,
+        );
+
+        assert_eq!(api_key, Ok(Some("flag".to_string())));
+    }
+
+    #[test]
+    fn keyring_key_beats_config_and_env_var() {
+        let api_key: Result<Option<String>, ()> = resolve_api_key(
+            None,
+            || Some("keyring".to_string()),
+            Some("config".to_string()),
+            unreachable,
+        );
+
+        assert_eq!(api_key, Ok(Some("keyring".to_string())));
+    }
+
+    #[test]
+    fn config_key_is_used_when_keyring_is_empty() {
+        let api_key: Result<Option<String>, ()> =
+            resolve_api_key(None, || None, Some("config".to_string()), unreachable);
+
+        assert_eq!(api_key, Ok(Some("config".to_string())));
+    }
+
+    #[test]
+    fn env_var_is_the_last_resort() {
+        let api_key: Result<Option<String>, ()> =
+            resolve_api_key(None, || None, None, || Ok(Some("env".to_string())));
+        let missing: Result<Option<String>, ()> = resolve_api_key(None, || None, None, || Ok(None));
+
+        assert_eq!(api_key, Ok(Some("env".to_string())));
+        assert_eq!(missing, Ok(None));
+    }
+}
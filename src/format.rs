@@ -0,0 +1,55 @@
+use crate::args::Locale;
+
+// render a number rounded to precision decimals, with the decimal separator
+// of the locale, trailing zeros are dropped so 14.0 renders as 14
+pub fn number(value: f32, precision: usize, locale: Locale) -> String {
+    let mut formatted: String = format!("{:.*}", precision, value);
+
+    if formatted.contains('.') {
+        formatted = formatted
+            .trim_end_matches('0')
+            .trim_end_matches('.')
+            .to_string();
+    }
+
+    // values rounding to zero from below shouldn't show a sign
+    if formatted == "-0" {
+        formatted = "0".to_string();
+    }
+
+    match locale {
+        Locale::EnUs => formatted,
+        Locale::European => formatted.replace('.', ","),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drops_trailing_zeros() {
+        assert_eq!(number(14.0, 1, Locale::EnUs), "14");
+        assert_eq!(number(14.25, 1, Locale::EnUs), "14.2");
+        assert_eq!(number(29.80, 2, Locale::EnUs), "29.8");
+    }
+
+    #[test]
+    fn keeps_zeros_before_the_decimal_point() {
+        assert_eq!(number(100.0, 0, Locale::EnUs), "100");
+        assert_eq!(number(1012.4, 0, Locale::EnUs), "1012");
+    }
+
+    #[test]
+    fn negative_zero_has_no_sign() {
+        assert_eq!(number(-0.04, 1, Locale::EnUs), "0");
+        assert_eq!(number(-0.4, 0, Locale::EnUs), "0");
+        assert_eq!(number(-1.5, 1, Locale::EnUs), "-1.5");
+    }
+
+    #[test]
+    fn european_locale_uses_decimal_comma() {
+        assert_eq!(number(12.5, 1, Locale::European), "12,5");
+        assert_eq!(number(12.0, 1, Locale::European), "12");
+    }
+}
@@ -0,0 +1,34 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// signal number of ctrl-c on every unix
+#[cfg(unix)]
+const SIGINT: i32 = 2;
+
+// set by the signal handler, polled by long running loops
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+#[cfg(unix)]
+extern "C" {
+    fn signal(signum: i32, handler: extern "C" fn(i32)) -> usize;
+}
+
+// only touches an atomic, which is async signal safe
+#[cfg(unix)]
+extern "C" fn on_interrupt(_signum: i32) {
+    INTERRUPTED.store(true, Ordering::SeqCst);
+}
+
+// record ctrl-c instead of terminating the process
+// elsewhere ctrl-c keeps its default behaviour
+pub fn install() {
+    #[cfg(unix)]
+    // SAFETY: the handler only stores to an atomic
+    unsafe {
+        signal(SIGINT, on_interrupt);
+    }
+}
+
+// whether ctrl-c was pressed since install
+pub fn requested() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
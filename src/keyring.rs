@@ -0,0 +1,112 @@
+#[cfg(target_os = "linux")]
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+// service the api key is stored under in the os secret store
+const SERVICE: &str = "cliweather";
+
+// account name of the api key within SERVICE
+const ACCOUNT: &str = "api_key";
+
+// api key saved by `login`, None when missing or no secret store is available
+pub fn load() -> Option<String> {
+    let output: Output = lookup_command()?.stderr(Stdio::null()).output().ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let api_key: String = String::from_utf8(output.stdout).ok()?.trim().to_string();
+
+    (!api_key.is_empty()).then_some(api_key)
+}
+
+// save api key to the os secret store, replacing any previous key
+pub fn store(api_key: &str) -> Result<(), String> {
+    let output: Output = store_output(api_key).map_err(|err| {
+        format!(
+            "{} could not be run: {}",
+            STORE_TOOL.unwrap_or("secret store"),
+            err
+        )
+    })?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "{} failed: {}",
+            STORE_TOOL.unwrap_or("secret store"),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(())
+}
+
+// command line tool talking to the platform secret store
+#[cfg(target_os = "linux")]
+const STORE_TOOL: Option<&str> = Some("secret-tool");
+#[cfg(target_os = "macos")]
+const STORE_TOOL: Option<&str> = Some("security");
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+const STORE_TOOL: Option<&str> = None;
+
+// libsecret, e.g. gnome keyring or kwallet
+#[cfg(target_os = "linux")]
+fn lookup_command() -> Option<Command> {
+    let mut command: Command = Command::new("secret-tool");
+    command.args(["lookup", "service", SERVICE, "account", ACCOUNT]);
+
+    Some(command)
+}
+
+// login keychain
+#[cfg(target_os = "macos")]
+fn lookup_command() -> Option<Command> {
+    let mut command: Command = Command::new("security");
+    command.args(["find-generic-password", "-s", SERVICE, "-a", ACCOUNT, "-w"]);
+
+    Some(command)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn lookup_command() -> Option<Command> {
+    None
+}
+
+// secret-tool reads the secret from stdin, keeping it out of the process list
+#[cfg(target_os = "linux")]
+fn store_output(api_key: &str) -> std::io::Result<Output> {
+    let mut child: std::process::Child = Command::new("secret-tool")
+        .args(["store", "--label=cliweather API key"])
+        .args(["service", SERVICE, "account", ACCOUNT])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(api_key.as_bytes())?;
+    }
+
+    child.wait_with_output()
+}
+
+// security only accepts the secret as an argument, -U updates an existing item
+#[cfg(target_os = "macos")]
+fn store_output(api_key: &str) -> std::io::Result<Output> {
+    Command::new("security")
+        .args(["add-generic-password", "-U", "-s", SERVICE, "-a", ACCOUNT])
+        .args(["-w", api_key])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .output()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn store_output(_api_key: &str) -> std::io::Result<Output> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "no secret store is supported on this platform",
+    ))
+}
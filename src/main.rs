@@ -1,28 +1,419 @@
-use weatherapi;
+mod args;
+mod color;
+mod config;
+mod format;
+mod interrupt;
+#[cfg(feature = "keyring")]
+mod keyring;
+mod output;
+mod recents;
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    dotenv::dotenv()?;
+use std::time::{Duration, Instant};
 
-    let api_key: String = std::env::var("API_KEY")?;
-    let location: String = std::env::var("LOCATION")?;
+use args::{Args, Command, Format, IconSet, Locale, Units};
+use config::Config;
+use serde::Serialize;
 
-    let weatherapi = weatherapi::WeatherAPI::new(&api_key, &location);
+// days fetched by `forecast` without --days
+const DEFAULT_FORECAST_DAYS: u8 = 3;
 
-    let weatherapi_response = weatherapi.fetch()?;
+// how often watch mode checks for ctrl-c while waiting to refresh
+const WATCH_POLL_INTERVAL: Duration = Duration::from_millis(100);
 
-    println!(
-        "Location:\n  Name: {}\n  Region: {} \n  Country: {}\n",
-        weatherapi_response.location().name(),
-        weatherapi_response.location().region(),
-        weatherapi_response.location().country()
-    );
+fn main() {
+    // print errors with Display, returning them from main would use Debug
+    if let Err(err) = run() {
+        eprintln!("Error: {}", err);
+        std::process::exit(1);
+    }
+}
+
+fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let args: Args =
+        Args::parse().map_err(|err| format!("{}\nRun `cliweather --help` for usage", err))?;
+
+    if args.help {
+        println!("{}", args::usage());
+        return Ok(());
+    }
+
+    let config: Config = Config::load()?;
+    let config_path: String = Config::path()
+        .map(|path| path.display().to_string())
+        .unwrap_or_else(|| "the config file".to_string());
+
+    if args.command == Command::Login {
+        return login(args.api_key);
+    }
+
+    if args.command == Command::Recents {
+        let recents: Vec<String> = recents::load();
+
+        if recents.is_empty() {
+            println!("No recent locations");
+        }
+        for (index, location) in recents.iter().enumerate() {
+            println!("{}  {}", index, location);
+        }
+
+        return Ok(());
+    }
+
+    // .env is optional, flags, config or env vars may be used instead
+    dotenv::dotenv().ok();
+
+    // flag > keyring > config > env var > error
+    let api_key: Option<String> =
+        config::resolve_api_key(args.api_key, keyring_api_key, config.api_key, || {
+            env_var("API_KEY")
+        })?;
+    let api_key: String = api_key.ok_or_else(|| {
+        format!(
+            "No API key found. Checked in order: --api-key flag, {}api_key in {}, API_KEY environment variable.\n\
+             Pass --api-key <key>, run `cliweather login`, add `api_key = \"<key>\"` to {} or run `export API_KEY=<key>`",
+            if cfg!(feature = "keyring") { "the OS keyring, " } else { "" },
+            config_path,
+            config_path
+        )
+    })?;
+    let locations: Vec<String> = if let Some(index) = args.recent {
+        let location: String = recents::load().get(index).cloned().ok_or_else(|| {
+            format!(
+                "No recent location at position {}, run `recents` to list them",
+                index
+            )
+        })?;
+
+        vec![location]
+    } else if args.locations.is_empty() && args.command != Command::Search {
+        let location: Option<String> = match config.default_location {
+            Some(location) => Some(location),
+            None => env_var("LOCATION")?,
+        };
+        let location: String = location.ok_or_else(|| {
+            format!(
+                "No location found. Checked in order: --location flag, default_location in {}, LOCATION environment variable.\n\
+                 Pass --location <city>, add `default_location = \"<city>\"` to {} or run `export LOCATION=<city>`",
+                config_path, config_path
+            )
+        })?;
+
+        vec![location]
+    } else {
+        args.locations
+    };
+    // without an explicit locale numbers keep the period decimal separator
+    let locale: Locale = args.locale.unwrap_or(Locale::EnUs);
+    // flag > config > locale > metric
+    let units: Units = args
+        .units
+        .or(config.units)
+        .or(args.locale.map(|locale| locale.units()))
+        .unwrap_or(Units::Metric);
+
+    if let Some(field) = &args.raw {
+        if !output::RAW_FIELDS.contains(&field.as_str()) {
+            return Err(format!(
+                "invalid value '{}' for --raw, expected one of: {}",
+                field,
+                output::RAW_FIELDS.join(", ")
+            )
+            .into());
+        }
+    }
+
+    let render: Render = Render {
+        format: args.format,
+        style: output::Style {
+            units,
+            locale,
+            precision: args.precision.unwrap_or(output::DEFAULT_PRECISION),
+            colored: color::enabled(),
+        },
+        raw: args.raw,
+        icon: args.icon.then(|| args.icon_set.unwrap_or(IconSet::Emoji)),
+    };
+
+    let interval: u64 = match args.command {
+        Command::Forecast => {
+            let days: u8 = args.days.unwrap_or(DEFAULT_FORECAST_DAYS);
+
+            return each_location(&api_key, &locations, &render, |weatherapi| {
+                Ok(weatherapi.fetch_forecast_daily(days)?)
+            });
+        }
+        Command::Astro => {
+            return each_location(&api_key, &locations, &render, |weatherapi| {
+                // default to today in the location's own timezone
+                let date: String = match &args.date {
+                    Some(date) => date.clone(),
+                    None => local_date(&weatherapi.fetch_timezone()?),
+                };
+
+                Ok(weatherapi.fetch_astronomy(&date)?)
+            });
+        }
+        Command::Search => {
+            let query: &str = args.query.as_deref().unwrap_or_default();
+            let results: Vec<weatherapi::SearchResult> =
+                weatherapi::WeatherAPI::new(&api_key, "").search(query)?;
 
-    let current_weather = weatherapi_response.current();
+            return print(&render, &results);
+        }
+        Command::Now | Command::Recents | Command::Login => match args.watch {
+            Some(interval) => interval,
+            None => return report(&api_key, &locations, &render),
+        },
+    };
 
+    // runs until ctrl-c, which is noticed between short sleeps
+    interrupt::install();
+
+    while !interrupt::requested() {
+        // clear screen and move the cursor home
+        print!("\x1b[2J\x1b[H");
+
+        if let Err(err) = report(&api_key, &locations, &render) {
+            eprintln!("{}", err);
+        }
+
+        let refresh: Instant = Instant::now() + Duration::from_secs(interval);
+
+        while !interrupt::requested() && Instant::now() < refresh {
+            std::thread::sleep(WATCH_POLL_INTERVAL);
+        }
+    }
+
+    // leave the shell prompt on a fresh line below the last report
+    println!();
+
+    Ok(())
+}
+
+// check api key + save it to the config file as plaintext, prompting when not passed
+fn login(api_key: Option<String>) -> Result<(), Box<dyn std::error::Error>> {
+    let api_key: String = match api_key {
+        Some(api_key) => api_key,
+        None => {
+            use std::io::Write;
+
+            print!("WeatherAPI key: ");
+            std::io::stdout().flush()?;
+
+            let mut line: String = String::new();
+            std::io::stdin().read_line(&mut line)?;
+            line
+        }
+    };
+    let api_key: &str = api_key.trim();
+
+    if api_key.is_empty() {
+        return Err("No API key entered".into());
+    }
+
+    weatherapi::WeatherAPI::new(api_key, "").verify_key()?;
+
+    #[cfg(feature = "keyring")]
+    match keyring::store(api_key) {
+        Ok(()) => {
+            println!("API key verified and saved to the OS keyring");
+            return Ok(());
+        }
+        Err(err) => eprintln!("Could not use the OS keyring, {}", err),
+    }
+
+    let path: std::path::PathBuf = Config::store_api_key(api_key)?;
+    // no os secret store is available, be upfront about where the key ends up
     println!(
-        "Current Weather:\n  Temperature (C): {}\n  Temperature (F): {}\n  Feels Like (C): {}\n  Feels Like (F): {}\n  Wind (mph): {}\n  Wind (km/h): {}\n  Wind Direction: {}\n  Condition: {}\n  Pressure (mb): {}\n  Pressure (in): {}",
-        current_weather.temp_c(), current_weather.temp_f(), current_weather.feelslike_c(), current_weather.feelslike_f(), current_weather.wind_mph(), current_weather.wind_kph(), current_weather.wind_dir(), current_weather.condition().text(), current_weather.pressure_mb(), current_weather.pressure_in()
+        "API key verified and saved in plaintext to {} (readable by your user only)",
+        path.display()
     );
 
     Ok(())
 }
+
+// api key saved by `login` in the os secret store, if built with it
+fn keyring_api_key() -> Option<String> {
+    #[cfg(feature = "keyring")]
+    {
+        keyring::load()
+    }
+    #[cfg(not(feature = "keyring"))]
+    {
+        None
+    }
+}
+
+// read env var, treating unset and blank values as missing
+fn env_var(name: &str) -> Result<Option<String>, String> {
+    match std::env::var(name) {
+        Ok(value) if !value.trim().is_empty() => Ok(Some(value)),
+        Ok(_) | Err(std::env::VarError::NotPresent) => Ok(None),
+        Err(std::env::VarError::NotUnicode(_)) => Err(format!(
+            "The {} environment variable is set but is not valid unicode, unset it or set it to a plain text value",
+            name
+        )),
+    }
+}
+
+// YYYY-MM-DD part of the local time reported by the api
+fn local_date(timezone: &weatherapi::TimeZone) -> String {
+    timezone
+        .localtime()
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+// how responses are printed
+struct Render {
+    format: Format,
+    style: output::Style,
+    // single undecorated field, overrides format
+    raw: Option<String>,
+    // condition glyph only, overrides format
+    icon: Option<IconSet>,
+}
+
+// fetch every location, reporting failures on stderr + recording successes
+// in the recents list, returns the responses and the number of failures
+fn fetch_each<T>(
+    api_key: &str,
+    locations: &[String],
+    fetch: impl Fn(&weatherapi::WeatherAPI) -> Result<T, Box<dyn std::error::Error>>,
+) -> (Vec<T>, usize) {
+    // one failing location shouldn't abort the others
+    let mut failed: usize = 0;
+    let mut responses: Vec<T> = Vec::new();
+    let mut succeeded: Vec<String> = Vec::new();
+
+    for location in locations {
+        let weatherapi = weatherapi::WeatherAPI::new(api_key, location);
+
+        match fetch(&weatherapi) {
+            Ok(response) => {
+                responses.push(response);
+                succeeded.push(location.clone());
+            }
+            Err(err) => {
+                eprintln!("{}: {}", location, err);
+                failed += 1;
+            }
+        }
+    }
+
+    recents::record(&succeeded);
+
+    (responses, failed)
+}
+
+// error summarising failed locations, if any failed
+fn failures(failed: usize, total: usize) -> Result<(), Box<dyn std::error::Error>> {
+    if failed > 0 {
+        return Err(format!("{} of {} locations failed", failed, total).into());
+    }
+
+    Ok(())
+}
+
+// fetch + print every location for subcommands other than now
+fn each_location<T: Printable>(
+    api_key: &str,
+    locations: &[String],
+    render: &Render,
+    fetch: impl Fn(&weatherapi::WeatherAPI) -> Result<T, Box<dyn std::error::Error>>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let (responses, failed): (Vec<T>, usize) = fetch_each(api_key, locations, fetch);
+
+    for response in &responses {
+        print(render, response)?;
+    }
+
+    failures(failed, locations.len())
+}
+
+// print a single response as json or text, other formats are now only
+fn print<T: Printable>(render: &Render, response: &T) -> Result<(), Box<dyn std::error::Error>> {
+    match render.format {
+        Format::Json => println!("{}", serde_json::to_string_pretty(response)?),
+        _ => println!("{}", response.text(&render.style)),
+    }
+
+    Ok(())
+}
+
+// responses printed by the forecast, astro and search subcommands
+trait Printable: Serialize {
+    fn text(&self, style: &output::Style) -> String;
+}
+
+impl Printable for weatherapi::DailyForecast {
+    fn text(&self, style: &output::Style) -> String {
+        output::forecast(self, style)
+    }
+}
+
+impl Printable for weatherapi::AstronomyResponse {
+    fn text(&self, style: &output::Style) -> String {
+        output::astro(self, style)
+    }
+}
+
+impl Printable for Vec<weatherapi::SearchResult> {
+    fn text(&self, _style: &output::Style) -> String {
+        output::search(self)
+    }
+}
+
+// fetch every location and print it in the requested format
+fn report(
+    api_key: &str,
+    locations: &[String],
+    render: &Render,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let (responses, failed): (Vec<weatherapi::Response>, usize) =
+        fetch_each(api_key, locations, |weatherapi| Ok(weatherapi.fetch()?));
+
+    if responses.is_empty() {
+        return failures(failed, locations.len());
+    }
+
+    if let Some(icon_set) = render.icon {
+        for response in &responses {
+            println!("{}", output::icon(response, icon_set));
+        }
+    } else if let Some(field) = &render.raw {
+        for response in &responses {
+            // field names are validated before fetching
+            println!("{}", output::raw(response, field).unwrap_or_default());
+        }
+    } else {
+        match render.format {
+            Format::Text => {
+                let blocks: Vec<String> = responses
+                    .iter()
+                    .map(|response| output::text(response, &render.style))
+                    .collect();
+
+                println!("{}", blocks.join("\n\n"));
+            }
+            Format::Json => {
+                for response in &responses {
+                    println!("{}", response.to_json_pretty()?);
+                }
+            }
+            Format::Table => println!("{}", output::table(&responses, &render.style)),
+            Format::Csv => println!("{}", output::csv(&responses)),
+            Format::Card => {
+                let width: usize = output::terminal_width();
+
+                for response in &responses {
+                    println!("{}", output::card(response, &render.style, width));
+                }
+            }
+        }
+    }
+
+    failures(failed, locations.len())
+}
@@ -0,0 +1,619 @@
+use weatherapi::{
+    Astro, AstronomyResponse, Current, DailyForecast, Day, Location, Response, SearchResult,
+};
+
+use crate::args::{IconSet, Locale, Units};
+use crate::color;
+use crate::format;
+
+/// Field names accepted by `--raw`, mapping to the getters of the response.
+pub const RAW_FIELDS: [&str; 20] = [
+    "name",
+    "country",
+    "localtime",
+    "last_updated",
+    "temp_c",
+    "temp_f",
+    "feelslike_c",
+    "feelslike_f",
+    "condition",
+    "humidity",
+    "cloud",
+    "uv",
+    "wind_kph",
+    "wind_mph",
+    "wind_dir",
+    "pressure_mb",
+    "pressure_in",
+    "precip_mm",
+    "precip_in",
+    "is_day",
+];
+
+// widest card drawn, even on wide terminals
+const CARD_MAX_WIDTH: usize = 48;
+
+// narrowest card that still fits the grid
+const CARD_MIN_WIDTH: usize = 30;
+
+// assumed terminal width when COLUMNS isn't set
+const DEFAULT_TERMINAL_WIDTH: usize = 80;
+
+// uv index from which the uv line is highlighted as a warning
+const UV_WARNING: f32 = 6.0;
+
+// shown in place of a field the response left out
+const NOT_AVAILABLE: &str = "n/a";
+
+// decimals shown for wind speeds + uv index
+const WIND_PRECISION: usize = 1;
+const UV_PRECISION: usize = 1;
+
+// decimals shown for pressure, inches need more to be useful
+const PRESSURE_MB_PRECISION: usize = 0;
+const PRESSURE_IN_PRECISION: usize = 2;
+
+/// Default number of decimals shown for temperatures.
+pub const DEFAULT_PRECISION: usize = 1;
+
+/// Style holds the presentation options for human readable output.
+pub struct Style {
+    pub units: Units,
+    pub locale: Locale,
+    // decimals shown for temperatures
+    pub precision: usize,
+    pub colored: bool,
+}
+
+// formatted values + unit labels for the requested unit system
+struct Measures {
+    temp: String,
+    feelslike: String,
+    temp_unit: &'static str,
+    wind: String,
+    wind_unit: &'static str,
+    pressure: String,
+    pressure_unit: &'static str,
+}
+
+impl Measures {
+    fn new(current: &Current, style: &Style) -> Measures {
+        let number = |value: f32, precision: usize| format::number(value, precision, style.locale);
+
+        match style.units {
+            Units::Metric => Measures {
+                temp: number(current.temp_c(), style.precision),
+                feelslike: number(current.feelslike_c(), style.precision),
+                temp_unit: "°C",
+                wind: number(current.wind_kph(), WIND_PRECISION),
+                wind_unit: "km/h",
+                pressure: number(current.pressure_mb(), PRESSURE_MB_PRECISION),
+                pressure_unit: "mb",
+            },
+            Units::Imperial => Measures {
+                temp: number(current.temp_f(), style.precision),
+                feelslike: number(current.feelslike_f(), style.precision),
+                temp_unit: "°F",
+                wind: number(current.wind_mph(), WIND_PRECISION),
+                wind_unit: "mph",
+                pressure: number(current.pressure_in(), PRESSURE_IN_PRECISION),
+                pressure_unit: "in",
+            },
+        }
+    }
+}
+
+// render human readable output in the requested unit system
+pub fn text(response: &Response, style: &Style) -> String {
+    let location: &Location = response.location();
+    let current: &Current = response.current();
+
+    let measures: Measures = Measures::new(current, style);
+    let colored: bool = style.colored;
+
+    let temp: String = color::paint(
+        &format!("{}{}", measures.temp, measures.temp_unit),
+        color::temperature(current.temp_c()),
+        colored,
+    );
+    let feelslike: String = color::paint(
+        &format!("{}{}", measures.feelslike, measures.temp_unit),
+        color::temperature(current.feelslike_c()),
+        colored,
+    );
+    let condition: String = format!(
+        "{} {}",
+        weatherapi::condition_emoji(current.condition().code(), current.is_day()),
+        color::paint(current.condition().text(), color::BOLD, colored)
+    );
+    let uv: String = match (current.uv(), current.uv_risk()) {
+        (Some(uv), Some(risk)) => {
+            let line: String = format!(
+                "{} ({})",
+                format::number(uv, UV_PRECISION, style.locale),
+                risk
+            );
+
+            if uv >= UV_WARNING {
+                color::paint(&line, color::YELLOW, colored)
+            } else {
+                line
+            }
+        }
+        _ => NOT_AVAILABLE.to_string(),
+    };
+
+    format!(
+        "{}, {}\nTemperature: {} (feels like {})\nCondition: {}\nWind: {} {} {}\nPressure: {} {}\nUV: {}",
+        location.name(),
+        location.country(),
+        temp,
+        feelslike,
+        condition,
+        measures.wind,
+        measures.wind_unit,
+        current.wind_dir(),
+        measures.pressure,
+        measures.pressure_unit,
+        uv
+    )
+}
+
+// render one line per forecast day in the requested unit system
+pub fn forecast(response: &DailyForecast, style: &Style) -> String {
+    let location: &Location = response.location();
+    let mut lines: Vec<String> = vec![color::paint(
+        &format!("{}, {}", location.name(), location.country()),
+        color::BOLD,
+        style.colored,
+    )];
+
+    for forecast_day in response.days() {
+        let day: &Day = forecast_day.day();
+        let (max, min, unit): (f32, f32, &str) = match style.units {
+            Units::Metric => (day.maxtemp_c(), day.mintemp_c(), "°C"),
+            Units::Imperial => (day.maxtemp_f(), day.mintemp_f(), "°F"),
+        };
+
+        lines.push(format!(
+            "{}  {}{} / {}{}  {}, {}% chance of rain",
+            forecast_day.date(),
+            format::number(max, style.precision, style.locale),
+            unit,
+            format::number(min, style.precision, style.locale),
+            unit,
+            day.condition().text(),
+            day.daily_chance_of_rain()
+        ));
+    }
+
+    lines.join("\n")
+}
+
+// render one line per search match
+pub fn search(results: &[SearchResult]) -> String {
+    if results.is_empty() {
+        return "No matching locations".to_string();
+    }
+
+    results
+        .iter()
+        .map(|result| {
+            format!(
+                "{}, {}, {} ({}, {})",
+                result.name(),
+                result.region(),
+                result.country(),
+                result.lat(),
+                result.lon()
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+// render sun and moon times for a location
+pub fn astro(response: &AstronomyResponse, style: &Style) -> String {
+    let location: &Location = response.location();
+    let astro: &Astro = response.astronomy().astro();
+
+    format!(
+        "{}\nSunrise: {}\nSunset: {}\nMoonrise: {}\nMoonset: {}\nMoon: {} ({}% illuminated)",
+        color::paint(
+            &format!("{}, {}", location.name(), location.country()),
+            color::BOLD,
+            style.colored,
+        ),
+        astro.sunrise(),
+        astro.sunset(),
+        astro.moonrise(),
+        astro.moonset(),
+        astro.moon_phase(),
+        astro.moon_illumination()
+    )
+}
+
+// render one row per location as an aligned ascii table
+pub fn table(responses: &[Response], style: &Style) -> String {
+    let header: Vec<String> = [
+        "Location",
+        "Temp",
+        "Feels Like",
+        "Condition",
+        "Wind",
+        "Humidity",
+    ]
+    .iter()
+    .map(|column| column.to_string())
+    .collect();
+    let mut rows: Vec<Vec<String>> = vec![header];
+
+    for response in responses {
+        let current: &Current = response.current();
+        let measures: Measures = Measures::new(current, style);
+
+        rows.push(vec![
+            format!(
+                "{}, {}",
+                response.location().name(),
+                response.location().country()
+            ),
+            format!("{}{}", measures.temp, measures.temp_unit),
+            format!("{}{}", measures.feelslike, measures.temp_unit),
+            current.condition().text().to_string(),
+            format!(
+                "{} {} {}",
+                measures.wind,
+                measures.wind_unit,
+                current.wind_dir()
+            ),
+            humidity(current),
+        ]);
+    }
+
+    let widths: Vec<usize> = (0..rows[0].len())
+        .map(|column| {
+            rows.iter()
+                .map(|row| display_width(&row[column]))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let border: String = widths
+        .iter()
+        .map(|width| "-".repeat(width + 2))
+        .collect::<Vec<String>>()
+        .join("+");
+    let border: String = format!("+{}+", border);
+
+    let mut lines: Vec<String> = vec![border.clone()];
+    for (index, row) in rows.iter().enumerate() {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!(" {}{} ", cell, " ".repeat(width - display_width(cell))))
+            .collect();
+        lines.push(format!("|{}|", cells.join("|")));
+
+        // separate header from data rows
+        if index == 0 {
+            lines.push(border.clone());
+        }
+    }
+    lines.push(border);
+
+    lines.join("\n")
+}
+
+// render header + one row per location as csv
+pub fn csv(responses: &[Response]) -> String {
+    let mut lines: Vec<String> =
+        vec!["location,country,temp_c,temp_f,humidity,wind_kph,condition".to_string()];
+
+    for response in responses {
+        let current: &Current = response.current();
+        let fields: [String; 7] = [
+            response.location().name().to_string(),
+            response.location().country().to_string(),
+            current.temp_c().to_string(),
+            current.temp_f().to_string(),
+            current
+                .humidity()
+                .map_or_else(String::new, |humidity| humidity.to_string()),
+            current.wind_kph().to_string(),
+            current.condition().text().to_string(),
+        ];
+
+        lines.push(
+            fields
+                .iter()
+                .map(|field| csv_field(field))
+                .collect::<Vec<String>>()
+                .join(","),
+        );
+    }
+
+    lines.join("\n")
+}
+
+// quote field if it contains a separator, quote or line break
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+// render a single field without decoration, None for unknown fields
+pub fn raw(response: &Response, field: &str) -> Option<String> {
+    let location: &Location = response.location();
+    let current: &Current = response.current();
+
+    // debug formatting keeps the decimal point, e.g. 14.0
+    let value: String = match field {
+        "name" => location.name().to_string(),
+        "country" => location.country().to_string(),
+        "localtime" => location.localtime().to_string(),
+        "last_updated" => current.last_updated().to_string(),
+        "temp_c" => format!("{:?}", current.temp_c()),
+        "temp_f" => format!("{:?}", current.temp_f()),
+        "feelslike_c" => format!("{:?}", current.feelslike_c()),
+        "feelslike_f" => format!("{:?}", current.feelslike_f()),
+        "condition" => current.condition().text().to_string(),
+        "humidity" => optional(current.humidity()),
+        "cloud" => optional(current.cloud()),
+        "uv" => optional(current.uv()),
+        "wind_kph" => format!("{:?}", current.wind_kph()),
+        "wind_mph" => format!("{:?}", current.wind_mph()),
+        "wind_dir" => current.wind_dir().to_string(),
+        "pressure_mb" => format!("{:?}", current.pressure_mb()),
+        "pressure_in" => format!("{:?}", current.pressure_in()),
+        "precip_mm" => optional(current.precip_mm()),
+        "precip_in" => optional(current.precip_in()),
+        "is_day" => current.is_day().to_string(),
+        _ => return None,
+    };
+
+    Some(value)
+}
+
+// raw value of a field the response may leave out, empty when absent
+fn optional(value: Option<f32>) -> String {
+    value.map_or_else(String::new, |value| format!("{:?}", value))
+}
+
+// humidity as a percentage, or a placeholder when absent
+fn humidity(current: &Current) -> String {
+    current.humidity().map_or_else(
+        || NOT_AVAILABLE.to_string(),
+        |humidity| format!("{}%", humidity),
+    )
+}
+
+// condition glyph for the current weather, nothing else
+pub fn icon(response: &Response, icon_set: IconSet) -> &'static str {
+    let current: &Current = response.current();
+    let code: u32 = current.condition().code();
+
+    match icon_set {
+        IconSet::Emoji => weatherapi::condition_emoji(code, current.is_day()),
+        IconSet::NerdFont => weatherapi::condition_nerdfont(code, current.is_day()),
+    }
+}
+
+// terminal width, COLUMNS if exported, otherwise the size of the
+// controlling tty, falling back to 80 columns when neither is available
+pub fn terminal_width() -> usize {
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.trim().parse().ok())
+        .or_else(tty_width)
+        .filter(|columns| *columns > 0)
+        .unwrap_or(DEFAULT_TERMINAL_WIDTH)
+}
+
+// ask stty for the size of the controlling tty, works with stdout piped too
+#[cfg(unix)]
+fn tty_width() -> Option<usize> {
+    let tty: std::fs::File = std::fs::File::open("/dev/tty").ok()?;
+    let output: std::process::Output = std::process::Command::new("stty")
+        .arg("size")
+        .stdin(tty)
+        .stderr(std::process::Stdio::null())
+        .output()
+        .ok()?;
+
+    // prints `rows columns`
+    String::from_utf8(output.stdout)
+        .ok()?
+        .split_whitespace()
+        .nth(1)?
+        .parse()
+        .ok()
+}
+
+#[cfg(not(unix))]
+fn tty_width() -> Option<usize> {
+    None
+}
+
+// render a bordered card with header, condition, temperature + detail grid
+pub fn card(response: &Response, style: &Style, terminal_width: usize) -> String {
+    let location: &Location = response.location();
+    let current: &Current = response.current();
+    let measures: Measures = Measures::new(current, style);
+
+    let width: usize = terminal_width.clamp(CARD_MIN_WIDTH, CARD_MAX_WIDTH);
+    // inside the left + right border
+    let inner: usize = width - 2;
+    // three grid cells share the inner width with two separators
+    let cell: usize = (inner - 2) / 3;
+    let cells: [usize; 3] = [cell, cell, inner - 2 - 2 * cell];
+
+    let rule = |left: &str, join: &str, right: &str, joined: bool| -> String {
+        let line: String = if joined {
+            cells
+                .iter()
+                .map(|cell| "─".repeat(*cell))
+                .collect::<Vec<String>>()
+                .join(join)
+        } else {
+            "─".repeat(inner)
+        };
+
+        format!("{}{}{}", left, line, right)
+    };
+    let row = |cells_text: [String; 3]| -> String {
+        let cells_text: Vec<String> = cells_text
+            .iter()
+            .zip(cells)
+            .map(|(text, cell)| fit(&format!(" {}", text), cell))
+            .collect();
+
+        format!("│{}│", cells_text.join("│"))
+    };
+
+    let lines: Vec<String> = vec![
+        rule("╭", "─", "╮", false),
+        format!(
+            "│{}│",
+            fit(
+                &format!(" {}, {}", location.name(), location.country()),
+                inner
+            )
+        ),
+        rule("├", "─", "┤", false),
+        format!(
+            "│{}│",
+            center(
+                weatherapi::condition_emoji(current.condition().code(), current.is_day()),
+                inner
+            )
+        ),
+        format!(
+            "│{}│",
+            center(
+                &format!(
+                    "{}{}  {}",
+                    measures.temp,
+                    measures.temp_unit,
+                    current.condition().text()
+                ),
+                inner
+            )
+        ),
+        format!(
+            "│{}│",
+            center(
+                &format!("feels like {}{}", measures.feelslike, measures.temp_unit),
+                inner
+            )
+        ),
+        rule("├", "┬", "┤", true),
+        row([
+            "Wind".to_string(),
+            "Humidity".to_string(),
+            "Pressure".to_string(),
+        ]),
+        row([
+            format!("{} {}", measures.wind, measures.wind_unit),
+            humidity(current),
+            format!("{} {}", measures.pressure, measures.pressure_unit),
+        ]),
+        rule("╰", "┴", "╯", true),
+    ];
+
+    lines.join("\n")
+}
+
+// columns text takes up in a terminal, emoji are two columns wide
+fn display_width(text: &str) -> usize {
+    text.chars().map(char_width).sum()
+}
+
+fn char_width(c: char) -> usize {
+    match c as u32 {
+        // variation selectors + zero width joiner
+        0xFE00..=0xFE0F | 0x200D => 0,
+        0x2600..=0x27BF | 0x1F000..=0x1FAFF => 2,
+        _ => 1,
+    }
+}
+
+// pad text with spaces to width, truncating with an ellipsis if too long
+fn fit(text: &str, width: usize) -> String {
+    if display_width(text) <= width {
+        return format!("{}{}", text, " ".repeat(width - display_width(text)));
+    }
+
+    let mut fitted: String = String::new();
+    for c in text.chars() {
+        if display_width(&fitted) + char_width(c) + 1 > width {
+            break;
+        }
+        fitted.push(c);
+    }
+    fitted.push('…');
+
+    format!("{}{}", fitted, " ".repeat(width - display_width(&fitted)))
+}
+
+// centre text within width, truncating if too long
+fn center(text: &str, width: usize) -> String {
+    let text_width: usize = display_width(text);
+
+    if text_width >= width {
+        return fit(text, width);
+    }
+
+    let left: usize = (width - text_width) / 2;
+
+    fit(&format!("{}{}", " ".repeat(left), text), width)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_aligns_wide_characters() {
+        let style: Style = Style {
+            units: Units::Metric,
+            locale: Locale::EnUs,
+            precision: DEFAULT_PRECISION,
+            colored: false,
+        };
+        let mut response: serde_json::Value =
+            serde_json::from_str(include_str!("../weatherapi/tests/fixtures/current.json"))
+                .unwrap();
+        let london: Response = serde_json::from_value(response.clone()).unwrap();
+        response["location"]["name"] = serde_json::json!("東京");
+        let tokyo: Response = serde_json::from_value(response).unwrap();
+
+        let table: String = table(&[london, tokyo], &style);
+        let widths: Vec<usize> = table.lines().map(display_width).collect();
+
+        assert!(widths.iter().all(|width| *width == widths[0]), "{}", table);
+    }
+
+    #[test]
+    fn csv_field_leaves_plain_text_alone() {
+        assert_eq!(csv_field("London"), "London");
+    }
+
+    #[test]
+    fn csv_field_quotes_separators_and_line_breaks() {
+        assert_eq!(
+            csv_field("City of London, Greater London"),
+            "\"City of London, Greater London\""
+        );
+        assert_eq!(csv_field("Light rain\nshower"), "\"Light rain\nshower\"");
+        assert_eq!(csv_field("a\r\nb"), "\"a\r\nb\"");
+    }
+
+    #[test]
+    fn csv_field_doubles_quotes() {
+        assert_eq!(csv_field("Saint \"Pete\""), "\"Saint \"\"Pete\"\"\"");
+    }
+}
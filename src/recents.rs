@@ -0,0 +1,47 @@
+use std::path::PathBuf;
+
+use crate::config::Config;
+
+// number of locations kept, oldest are dropped first
+const MAX_RECENTS: usize = 10;
+
+// recents file, stored next to the config file
+fn path() -> Option<PathBuf> {
+    Some(Config::path()?.with_file_name("recents.json"))
+}
+
+// recently queried locations, most recent first
+// a missing or unreadable file is treated as empty
+pub fn load() -> Vec<String> {
+    path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+// move locations to the front of the recents file
+// failing to write shouldn't stop the cli, so errors are ignored
+pub fn record(locations: &[String]) {
+    let path: PathBuf = match path() {
+        Some(path) => path,
+        None => return,
+    };
+
+    let mut recents: Vec<String> = locations.to_vec();
+    for existing in load() {
+        if !recents
+            .iter()
+            .any(|location| location.eq_ignore_ascii_case(&existing))
+        {
+            recents.push(existing);
+        }
+    }
+    recents.truncate(MAX_RECENTS);
+
+    if let Ok(contents) = serde_json::to_string_pretty(&recents) {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).ok();
+        }
+        std::fs::write(&path, contents).ok();
+    }
+}
@@ -0,0 +1,214 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::{redact, Error, RequestInfo, Response, WeatherAPI, CURRENT_ENDPOINT};
+
+/// Default time a cached response is considered fresh.
+pub const DEFAULT_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// CachedWeatherAPI wraps a `WeatherAPI` and keeps the last response per
+/// location in memory, avoiding repeated requests while it is still fresh.
+pub struct CachedWeatherAPI {
+    weatherapi: WeatherAPI,
+    ttl: Duration,
+    entries: Mutex<HashMap<String, (Instant, Response)>>,
+    disk_dir: Option<PathBuf>,
+    coordinate_decimals: Option<u32>,
+}
+
+/// CachedResponse is a response returned by the cache, tagged with whether it
+/// is a stale copy served from disk because the network was unavailable.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CachedResponse {
+    response: Response,
+    stale: bool,
+}
+
+/// Getters for the `response` and `stale` fields of the `CachedResponse` struct.
+impl CachedResponse {
+    pub fn response(&self) -> &Response {
+        &self.response
+    }
+
+    pub fn into_response(self) -> Response {
+        self.response
+    }
+
+    pub fn is_stale(&self) -> bool {
+        self.stale
+    }
+}
+
+impl CachedWeatherAPI {
+    // initialiser for CachedWeatherAPI
+    // responses are reused until they are older than ttl
+    pub fn new(weatherapi: WeatherAPI, ttl: Duration) -> CachedWeatherAPI {
+        CachedWeatherAPI {
+            weatherapi,
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+            disk_dir: None,
+            coordinate_decimals: None,
+        }
+    }
+
+    // persist the last successful response per location as json under dir
+    pub fn with_disk_cache(mut self, dir: impl Into<PathBuf>) -> CachedWeatherAPI {
+        self.disk_dir = Some(dir.into());
+        self
+    }
+
+    // round "lat,lon" locations to decimals places when forming the cache key
+    // 3 decimals is roughly 100m, so a moving gps position keeps hitting the
+    // cache, at the cost of serving a response for a point up to that far away
+    pub fn with_coordinate_rounding(mut self, decimals: u32) -> CachedWeatherAPI {
+        self.coordinate_decimals = Some(decimals);
+        self
+    }
+
+    // fetch current weather for configured location, using cache when fresh
+    pub fn fetch_cached(&self) -> Result<Response, Error> {
+        self.fetch_cached_for(&self.weatherapi.location)
+    }
+
+    // fetch current weather for location, using cache when fresh
+    pub fn fetch_cached_for(&self, location: &str) -> Result<Response, Error> {
+        let key: String = self.key(location);
+
+        let started: Instant = Instant::now();
+        let mut weatherapi: WeatherAPI = self.weatherapi.clone();
+        weatherapi.location = location.to_string();
+
+        let cached: Option<Response> = self
+            .lock()
+            .get(&key)
+            .filter(|(fetched_at, _)| fetched_at.elapsed() < self.ttl)
+            .map(|(_, response)| response.clone());
+        if let Some(response) = cached {
+            weatherapi.observe(RequestInfo {
+                url: weatherapi
+                    .prepare_url(CURRENT_ENDPOINT, &[])
+                    .map(|url| redact(&url))
+                    .unwrap_or_default(),
+                status: None,
+                elapsed: started.elapsed(),
+                cache_hit: true,
+            });
+
+            return Ok(response);
+        }
+
+        let response: Response = weatherapi.fetch()?;
+
+        if let Some(path) = self.disk_path(&key) {
+            // disk cache is best effort, failing to write shouldn't fail the fetch
+            write_response(&path, &response).ok();
+        }
+        self.lock().insert(key, (Instant::now(), response.clone()));
+
+        Ok(response)
+    }
+
+    // fetch current weather for configured location, falling back to disk cache
+    pub fn fetch_with_fallback(&self) -> Result<CachedResponse, Error> {
+        self.fetch_with_fallback_for(&self.weatherapi.location)
+    }
+
+    // fetch current weather for location, falling back to the last response
+    // stored on disk when the network is unavailable
+    pub fn fetch_with_fallback_for(&self, location: &str) -> Result<CachedResponse, Error> {
+        match self.fetch_cached_for(location) {
+            Ok(response) => Ok(CachedResponse {
+                response,
+                stale: false,
+            }),
+            // api + auth errors are returned as is, only network errors fall back
+            Err(err) if err.is_network_error() => {
+                match self
+                    .disk_path(&self.key(location))
+                    .and_then(|path| read_response(&path))
+                {
+                    Some(response) => Ok(CachedResponse {
+                        response,
+                        stale: true,
+                    }),
+                    None => Err(err),
+                }
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    // remove all cached responses
+    pub fn clear_cache(&self) {
+        self.lock().clear();
+    }
+
+    // cache key for location, rounding coordinates if configured
+    fn key(&self, location: &str) -> String {
+        let key: String = normalize(location);
+
+        match self.coordinate_decimals {
+            Some(decimals) => round_coordinates(&key, decimals).unwrap_or(key),
+            None => key,
+        }
+    }
+
+    // disk cache path for key, if disk cache is enabled
+    fn disk_path(&self, key: &str) -> Option<PathBuf> {
+        self.disk_dir.as_ref().map(|dir| dir.join(file_name(key)))
+    }
+
+    // a poisoned lock only means another fetch panicked, the map is still usable
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, (Instant, Response)>> {
+        self.entries.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+// file name for cache key, escaping characters unsafe in paths
+fn file_name(key: &str) -> String {
+    let mut name: String = String::new();
+
+    for byte in key.bytes() {
+        if byte.is_ascii_alphanumeric() || byte == b'-' {
+            name.push(byte as char);
+        } else {
+            name.push_str(&format!("_{:02x}", byte));
+        }
+    }
+
+    name + ".json"
+}
+
+// write response to disk as json
+fn write_response(path: &Path, response: &Response) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    std::fs::write(path, serde_json::to_vec(response)?)
+}
+
+// read previously written response from disk
+fn read_response(path: &Path) -> Option<Response> {
+    let contents: Vec<u8> = std::fs::read(path).ok()?;
+
+    serde_json::from_slice(&contents).ok()
+}
+
+// cache key for location, ignoring case + surrounding whitespace
+fn normalize(location: &str) -> String {
+    location.trim().to_lowercase()
+}
+
+// round both parts of a "lat,lon" location, None for other locations
+fn round_coordinates(location: &str, decimals: u32) -> Option<String> {
+    let (lat, lon): (&str, &str) = location.split_once(',')?;
+    let lat: f64 = lat.trim().parse().ok()?;
+    let lon: f64 = lon.trim().parse().ok()?;
+    let decimals: usize = decimals as usize;
+
+    Some(format!("{:.*},{:.*}", decimals, lat, decimals, lon))
+}
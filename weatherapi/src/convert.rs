@@ -0,0 +1,96 @@
+//! Unit conversions used by the response types, usable without any request.
+
+// offset between celsius and kelvin
+const KELVIN_OFFSET: f32 = 273.15;
+
+// km/h in one m/s
+const KPH_PER_MS: f32 = 3.6;
+
+// km/h in one mph, from the international mile of 1609.344 m
+const KPH_PER_MPH: f32 = 1.609_344;
+
+// millibars in one inch of mercury
+const MB_PER_INHG: f32 = 33.863_89;
+
+// millibars in one standard atmosphere
+const MB_PER_ATM: f32 = 1013.25;
+
+// celsius to fahrenheit
+pub fn c_to_f(celsius: f32) -> f32 {
+    celsius * 9.0 / 5.0 + 32.0
+}
+
+// fahrenheit to celsius
+pub fn f_to_c(fahrenheit: f32) -> f32 {
+    (fahrenheit - 32.0) * 5.0 / 9.0
+}
+
+// celsius to kelvin
+pub fn c_to_k(celsius: f32) -> f32 {
+    celsius + KELVIN_OFFSET
+}
+
+// kelvin to celsius
+pub fn k_to_c(kelvin: f32) -> f32 {
+    kelvin - KELVIN_OFFSET
+}
+
+// fahrenheit to kelvin
+pub fn f_to_k(fahrenheit: f32) -> f32 {
+    c_to_k(f_to_c(fahrenheit))
+}
+
+// kelvin to fahrenheit
+pub fn k_to_f(kelvin: f32) -> f32 {
+    c_to_f(k_to_c(kelvin))
+}
+
+// km/h to mph
+pub fn kph_to_mph(kph: f32) -> f32 {
+    kph / KPH_PER_MPH
+}
+
+// mph to km/h
+pub fn mph_to_kph(mph: f32) -> f32 {
+    mph * KPH_PER_MPH
+}
+
+// km/h to m/s
+pub fn kph_to_ms(kph: f32) -> f32 {
+    kph / KPH_PER_MS
+}
+
+// m/s to km/h
+pub fn ms_to_kph(ms: f32) -> f32 {
+    ms * KPH_PER_MS
+}
+
+// mph to m/s
+pub fn mph_to_ms(mph: f32) -> f32 {
+    kph_to_ms(mph_to_kph(mph))
+}
+
+// m/s to mph
+pub fn ms_to_mph(ms: f32) -> f32 {
+    kph_to_mph(ms_to_kph(ms))
+}
+
+// millibars (or hectopascals) to inches of mercury
+pub fn mb_to_inhg(mb: f32) -> f32 {
+    mb / MB_PER_INHG
+}
+
+// inches of mercury to millibars
+pub fn inhg_to_mb(inhg: f32) -> f32 {
+    inhg * MB_PER_INHG
+}
+
+// millibars to standard atmospheres
+pub fn mb_to_atm(mb: f32) -> f32 {
+    mb / MB_PER_ATM
+}
+
+// standard atmospheres to millibars
+pub fn atm_to_mb(atm: f32) -> f32 {
+    atm * MB_PER_ATM
+}
@@ -0,0 +1,75 @@
+use std::time::Duration;
+
+use crate::Error;
+
+/// HttpResponse is a completed http request, independent of the backend used
+/// to perform it.
+pub struct HttpResponse {
+    pub(crate) status: u16,
+    pub(crate) body: String,
+    // delay requested by the server via `Retry-After`, seconds form only
+    pub(crate) retry_after: Option<Duration>,
+}
+
+impl HttpResponse {
+    // response with the given status code + body
+    pub fn new(status: u16, body: String) -> HttpResponse {
+        HttpResponse {
+            status,
+            body,
+            retry_after: None,
+        }
+    }
+
+    // delay the server asked for before retrying, e.g. from `Retry-After`
+    pub fn with_retry_after(mut self, retry_after: Duration) -> HttpResponse {
+        self.retry_after = Some(retry_after);
+        self
+    }
+}
+
+/// Backend performs the http requests made by `WeatherAPI`. Responses with a
+/// non-200 status are still returned as responses, only requests that could
+/// not be completed are errors, reported as `Error::BackendFailed`.
+///
+/// Implement it to reuse an existing http client, see `WeatherAPI::with_backend`.
+/// With the default `backend-ureq` feature a pooled `ureq::Agent` is used when
+/// no backend is set, without it every `WeatherAPI` needs one.
+pub trait Backend: Send + Sync {
+    fn get(&self, url: &str) -> Result<HttpResponse, Error>;
+
+    fn post_json(&self, url: &str, body: &serde_json::Value) -> Result<HttpResponse, Error>;
+}
+
+#[cfg(feature = "backend-ureq")]
+impl Backend for ureq::Agent {
+    fn get(&self, url: &str) -> Result<HttpResponse, Error> {
+        into_http_response(ureq::Agent::get(self, url).call())
+    }
+
+    fn post_json(&self, url: &str, body: &serde_json::Value) -> Result<HttpResponse, Error> {
+        into_http_response(ureq::Agent::post(self, url).send_json(body))
+    }
+}
+
+// ureq reports non-2xx statuses as errors, convert them back into responses
+#[cfg(feature = "backend-ureq")]
+fn into_http_response(result: Result<ureq::Response, ureq::Error>) -> Result<HttpResponse, Error> {
+    let response: ureq::Response = match result {
+        Ok(response) => response,
+        Err(ureq::Error::Status(_, response)) => response,
+        Err(err) => return Err(err.into()),
+    };
+    let status: u16 = response.status();
+    let retry_after: Option<Duration> = response
+        .header("Retry-After")
+        .and_then(|value| value.trim().parse().ok())
+        .map(Duration::from_secs);
+    let body: String = response.into_string()?;
+
+    Ok(HttpResponse {
+        status,
+        body,
+        retry_after,
+    })
+}
@@ -0,0 +1,36 @@
+use std::time::Instant;
+
+// token bucket allowing bursts up to the per minute budget
+pub(crate) struct RateLimiter {
+    capacity: f64,
+    tokens: f64,
+    refill_per_sec: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(calls_per_minute: u32) -> RateLimiter {
+        RateLimiter {
+            capacity: calls_per_minute as f64,
+            tokens: calls_per_minute as f64,
+            refill_per_sec: calls_per_minute as f64 / 60.0,
+            last_refill: Instant::now(),
+        }
+    }
+
+    // take one token, false when the budget is exhausted
+    pub(crate) fn try_acquire(&mut self) -> bool {
+        let now: Instant = Instant::now();
+        let elapsed: f64 = now.duration_since(self.last_refill).as_secs_f64();
+
+        self.tokens = (self.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        self.last_refill = now;
+
+        if self.tokens < 1.0 {
+            return false;
+        }
+
+        self.tokens -= 1.0;
+        true
+    }
+}
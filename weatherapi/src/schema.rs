@@ -0,0 +1,107 @@
+use serde_json::{json, Map, Value};
+
+// json schema of `Response` and the location/current/condition shapes
+// written out by hand, keep in sync with the serde structs in lib.rs
+pub(crate) fn response() -> Value {
+    let condition: Value = object(&[("text", string()), ("icon", string()), ("code", integer())]);
+    let location: Value = object(&[
+        ("name", string()),
+        ("region", string()),
+        ("country", string()),
+        ("lat", number()),
+        ("lon", number()),
+        ("tz_id", string()),
+        ("localtime", string()),
+        ("localtime_epoch", integer()),
+    ]);
+    let mut current: Value = object(&[
+        ("temp_c", number()),
+        ("temp_f", number()),
+        ("feelslike_c", number()),
+        ("feelslike_f", number()),
+        ("wind_mph", number()),
+        ("wind_kph", number()),
+        ("wind_degree", number()),
+        ("wind_dir", string()),
+        ("condition", condition),
+        ("pressure_mb", number()),
+        ("pressure_in", number()),
+        ("humidity", number()),
+        ("uv", number()),
+        ("cloud", number()),
+        ("vis_km", number()),
+        ("vis_miles", number()),
+        ("gust_mph", number()),
+        ("gust_kph", number()),
+        ("precip_mm", number()),
+        ("precip_in", number()),
+        ("is_day", integer()),
+        ("last_updated", string()),
+        ("last_updated_epoch", integer()),
+    ]);
+    // omitted by some plans and endpoints
+    optional(
+        &mut current,
+        &[
+            "humidity",
+            "uv",
+            "cloud",
+            "vis_km",
+            "vis_miles",
+            "gust_mph",
+            "gust_kph",
+            "precip_mm",
+            "precip_in",
+            "is_day",
+            "last_updated",
+            "last_updated_epoch",
+        ],
+    );
+    // only present when requested with aqi=yes
+    current["properties"]["air_quality"] = object(&[
+        ("co", number()),
+        ("no2", number()),
+        ("o3", number()),
+        ("so2", number()),
+        ("pm2_5", number()),
+        ("pm10", number()),
+        ("us-epa-index", integer()),
+        ("gb-defra-index", integer()),
+    ]);
+
+    let mut schema: Value = object(&[("location", location), ("current", current)]);
+    schema["$schema"] = json!("http://json-schema.org/draft-07/schema#");
+    schema["title"] = json!("Response");
+
+    schema
+}
+
+// object schema requiring every listed property
+fn object(properties: &[(&str, Value)]) -> Value {
+    let required: Vec<&str> = properties.iter().map(|(name, _)| *name).collect();
+    let properties: Map<String, Value> = properties
+        .iter()
+        .map(|(name, schema)| (name.to_string(), schema.clone()))
+        .collect();
+
+    json!({ "type": "object", "properties": properties, "required": required })
+}
+
+// drop names from the required properties of an object schema
+fn optional(schema: &mut Value, names: &[&str]) {
+    if let Some(required) = schema["required"].as_array_mut() {
+        required.retain(|name| !names.contains(&name.as_str().unwrap_or_default()));
+    }
+}
+
+fn number() -> Value {
+    json!({ "type": "number" })
+}
+
+fn integer() -> Value {
+    json!({ "type": "integer" })
+}
+
+fn string() -> Value {
+    json!({ "type": "string" })
+}
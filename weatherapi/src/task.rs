@@ -0,0 +1,66 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+/// Task runs a blocking closure on a dedicated thread and resolves once the
+/// closure returns, re-raising its panic if it panicked. This keeps async
+/// callers from blocking their executor without tying the crate to a
+/// specific async runtime.
+pub(crate) struct Task<T> {
+    shared: Arc<Mutex<Shared<T>>>,
+}
+
+// state shared between the worker thread and the future
+struct Shared<T> {
+    // Err holds the panic payload of the closure
+    result: Option<std::thread::Result<T>>,
+    waker: Option<Waker>,
+}
+
+// spawn closure on a new thread + return future resolving to its result
+pub(crate) fn spawn<T, F>(f: F) -> Task<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let shared: Arc<Mutex<Shared<T>>> = Arc::new(Mutex::new(Shared {
+        result: None,
+        waker: None,
+    }));
+    let worker: Arc<Mutex<Shared<T>>> = Arc::clone(&shared);
+
+    std::thread::spawn(move || {
+        // a panic must still resolve the task, otherwise it stays pending forever
+        let result: std::thread::Result<T> =
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(f));
+        let mut shared = worker.lock().unwrap_or_else(|err| err.into_inner());
+
+        shared.result = Some(result);
+        if let Some(waker) = shared.waker.take() {
+            waker.wake();
+        }
+    });
+
+    Task { shared }
+}
+
+impl<T> Future for Task<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let mut shared = self.shared.lock().unwrap_or_else(|err| err.into_inner());
+
+        match shared.result.take() {
+            Some(Ok(result)) => Poll::Ready(result),
+            Some(Err(payload)) => {
+                drop(shared);
+                std::panic::resume_unwind(payload)
+            }
+            None => {
+                shared.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
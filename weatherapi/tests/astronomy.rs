@@ -0,0 +1,54 @@
+use std::time::Duration;
+
+use serde_json::json;
+use weatherapi::{daylight_remaining, Astronomy};
+
+fn astronomy(sunrise: &str, sunset: &str) -> Astronomy {
+    serde_json::from_value(json!({
+        "astro": {
+            "sunrise": sunrise,
+            "sunset": sunset,
+            "moonrise": "10:02 AM",
+            "moonset": "08:19 PM",
+            "moon_phase": "Waxing Crescent",
+            "moon_illumination": 19
+        }
+    }))
+    .unwrap()
+}
+
+#[test]
+fn daylight_remaining_during_the_day() {
+    let astro: Astronomy = astronomy("07:59 AM", "04:23 PM");
+
+    assert_eq!(
+        daylight_remaining(&astro, "2024-01-15 14:35"),
+        Some(Duration::from_secs(108 * 60))
+    );
+}
+
+#[test]
+fn daylight_remaining_outside_daylight() {
+    let astro: Astronomy = astronomy("07:59 AM", "04:23 PM");
+
+    assert_eq!(
+        daylight_remaining(&astro, "2024-01-15 6:00"),
+        Some(Duration::from_secs(504 * 60))
+    );
+    assert_eq!(
+        daylight_remaining(&astro, "2024-01-15 20:00"),
+        Some(Duration::ZERO)
+    );
+}
+
+#[test]
+fn daylight_remaining_polar_day_or_night() {
+    assert_eq!(
+        daylight_remaining(&astronomy("No sunrise", "No sunset"), "2024-01-15 12:00"),
+        None
+    );
+    assert_eq!(
+        daylight_remaining(&astronomy("09:12 AM", "No sunset"), "2024-06-21 12:00"),
+        None
+    );
+}
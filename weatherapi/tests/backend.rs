@@ -0,0 +1,67 @@
+use std::sync::{Arc, Mutex};
+
+use weatherapi::{Backend, Error, HttpResponse, Response, WeatherAPI};
+
+const CURRENT: &str = include_str!("fixtures/current.json");
+
+// backend replaying canned results, recording every requested url
+struct Canned {
+    results: Mutex<Vec<Result<HttpResponse, Error>>>,
+    urls: Mutex<Vec<String>>,
+}
+
+impl Canned {
+    fn new(results: Vec<Result<HttpResponse, Error>>) -> Arc<Canned> {
+        Arc::new(Canned {
+            results: Mutex::new(results),
+            urls: Mutex::new(Vec::new()),
+        })
+    }
+}
+
+impl Backend for Canned {
+    fn get(&self, url: &str) -> Result<HttpResponse, Error> {
+        self.urls.lock().unwrap().push(url.to_string());
+        self.results.lock().unwrap().remove(0)
+    }
+
+    fn post_json(&self, url: &str, _body: &serde_json::Value) -> Result<HttpResponse, Error> {
+        self.get(url)
+    }
+}
+
+#[test]
+fn custom_backend_performs_requests() {
+    let backend: Arc<Canned> = Canned::new(vec![Ok(HttpResponse::new(200, CURRENT.to_string()))]);
+    let mut weatherapi = WeatherAPI::new("key", "London");
+    weatherapi.with_backend(backend.clone());
+
+    let response: Response = weatherapi.fetch().unwrap();
+
+    assert_eq!(response.location().name(), "London");
+    assert!(backend.urls.lock().unwrap()[0].contains("q=London"));
+}
+
+#[test]
+fn backend_failures_are_retried_and_redacted() {
+    let backend: Arc<Canned> = Canned::new(vec![
+        Err(Error::BackendFailed(
+            "connection reset for https://api.weatherapi.com/v1/current.json?key=secret"
+                .to_string(),
+        )),
+        Ok(HttpResponse::new(200, CURRENT.to_string())),
+    ]);
+    let weatherapi = WeatherAPI::builder()
+        .api_key("key")
+        .location("London")
+        .retries(1)
+        .backend(backend.clone())
+        .build()
+        .unwrap();
+    assert!(weatherapi.fetch().is_ok());
+    assert_eq!(backend.urls.lock().unwrap().len(), 2);
+
+    let err: Error = Error::BackendFailed("timed out, url ?key=secret".to_string());
+    assert!(!err.to_string().contains("secret"));
+    assert!(!format!("{:?}", err).contains("secret"));
+}
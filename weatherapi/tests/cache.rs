@@ -0,0 +1,104 @@
+mod common;
+
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use weatherapi::{CachedResponse, CachedWeatherAPI, Error, RequestInfo, WeatherAPI, DEFAULT_TTL};
+
+const CURRENT: &str = include_str!("fixtures/current.json");
+const ERROR_NOT_FOUND: &str = include_str!("fixtures/error_1006.json");
+
+// empty disk cache directory unique to the test
+fn cache_dir(name: &str) -> PathBuf {
+    let dir: PathBuf =
+        std::env::temp_dir().join(format!("weatherapi-{}-{}", name, std::process::id()));
+    std::fs::remove_dir_all(&dir).ok();
+
+    dir
+}
+
+#[test]
+fn nearby_coordinates_share_a_cache_entry() {
+    let mut weatherapi = WeatherAPI::new("key", "London");
+    // the server answers once, a second request would fail to connect
+    weatherapi.with_base_url(&common::serve_once(200, CURRENT));
+    let cached: CachedWeatherAPI =
+        CachedWeatherAPI::new(weatherapi, DEFAULT_TTL).with_coordinate_rounding(3);
+
+    let first = cached.fetch_cached_for("51.50012,-0.12001").unwrap();
+    let second = cached.fetch_cached_for("51.50049, -0.12013").unwrap();
+
+    assert_eq!(first, second);
+}
+
+#[test]
+fn coordinates_are_not_rounded_by_default() {
+    let mut weatherapi = WeatherAPI::new("key", "London");
+    weatherapi.with_base_url(&common::serve_once(200, CURRENT));
+    let cached: CachedWeatherAPI = CachedWeatherAPI::new(weatherapi, DEFAULT_TTL);
+
+    cached.fetch_cached_for("51.50012,-0.12001").unwrap();
+
+    assert!(cached.fetch_cached_for("51.50049,-0.12013").is_err());
+}
+
+#[test]
+fn observer_sees_requests_and_cache_hits() {
+    let seen: Arc<Mutex<Vec<RequestInfo>>> = Arc::new(Mutex::new(Vec::new()));
+    let observed: Arc<Mutex<Vec<RequestInfo>>> = Arc::clone(&seen);
+    let mut weatherapi = WeatherAPI::new("secret-key", "London");
+    weatherapi.with_base_url(&common::serve_once(200, CURRENT));
+    weatherapi.with_request_observer(move |info| observed.lock().unwrap().push(info.clone()));
+    let cached: CachedWeatherAPI = CachedWeatherAPI::new(weatherapi, DEFAULT_TTL);
+
+    cached.fetch_cached().unwrap();
+    cached.fetch_cached().unwrap();
+
+    let seen = seen.lock().unwrap();
+    assert_eq!(seen.len(), 2);
+    assert_eq!(seen[0].status(), Some(200));
+    assert!(!seen[0].cache_hit());
+    assert!(seen[1].cache_hit());
+    assert!(seen.iter().all(|info| !info.url().contains("secret-key")));
+}
+
+#[test]
+fn network_failure_falls_back_to_disk_cache() {
+    let dir: PathBuf = cache_dir("fallback");
+    let mut weatherapi = WeatherAPI::new("key", "London");
+    // answers once, the second request fails to connect
+    weatherapi.with_base_url(&common::serve_once(200, CURRENT));
+    // expire entries straight away so the second call goes to the network
+    let cached: CachedWeatherAPI =
+        CachedWeatherAPI::new(weatherapi, Duration::ZERO).with_disk_cache(&dir);
+
+    let fresh: CachedResponse = cached.fetch_with_fallback().unwrap();
+    let offline: CachedResponse = cached.fetch_with_fallback().unwrap();
+
+    assert!(!fresh.is_stale());
+    assert!(offline.is_stale());
+    assert_eq!(offline.response(), fresh.response());
+
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn api_error_does_not_fall_back() {
+    let dir: PathBuf = cache_dir("api-error");
+    let (base_url, _) = common::serve_sequence(vec![
+        (200, Vec::new(), CURRENT),
+        (400, Vec::new(), ERROR_NOT_FOUND),
+    ]);
+    let mut weatherapi = WeatherAPI::new("key", "London");
+    weatherapi.with_base_url(&base_url);
+    let cached: CachedWeatherAPI =
+        CachedWeatherAPI::new(weatherapi, Duration::ZERO).with_disk_cache(&dir);
+
+    cached.fetch_with_fallback().unwrap();
+    let err: Error = cached.fetch_with_fallback().unwrap_err();
+
+    assert!(err.is_not_found());
+
+    std::fs::remove_dir_all(&dir).ok();
+}
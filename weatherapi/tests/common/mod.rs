@@ -0,0 +1,142 @@
+// shared between test crates, not every crate uses every helper
+#![allow(dead_code)]
+
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::mpsc::{self, Receiver};
+
+use serde_json::{json, Value};
+use weatherapi::{Current, Response};
+
+// sample current.json response, fields can be overridden per test
+// overriding a field with null drops it from the response
+pub fn response(current_overrides: Value) -> Response {
+    let mut current: Value = json!({
+        "last_updated_epoch": 1705329000,
+        "last_updated": "2024-01-15 14:30",
+        "temp_c": 14.0,
+        "temp_f": 57.2,
+        "is_day": 1,
+        "condition": { "text": "Partly cloudy", "icon": "//cdn.weatherapi.com/weather/64x64/day/116.png", "code": 1003 },
+        "wind_mph": 6.9,
+        "wind_kph": 11.2,
+        "wind_degree": 320,
+        "wind_dir": "NW",
+        "pressure_mb": 1012.0,
+        "pressure_in": 29.88,
+        "precip_mm": 0.0,
+        "precip_in": 0.0,
+        "humidity": 72,
+        "cloud": 50,
+        "feelslike_c": 12.9,
+        "feelslike_f": 55.2,
+        "vis_km": 10.0,
+        "vis_miles": 6.0,
+        "uv": 3.0,
+        "gust_mph": 9.8,
+        "gust_kph": 15.8
+    });
+
+    if let (Some(current), Some(overrides)) =
+        (current.as_object_mut(), current_overrides.as_object())
+    {
+        for (key, value) in overrides {
+            match value {
+                Value::Null => current.remove(key),
+                value => current.insert(key.clone(), value.clone()),
+            };
+        }
+    }
+
+    serde_json::from_value(json!({
+        "location": {
+            "name": "London",
+            "region": "City of London, Greater London",
+            "country": "United Kingdom",
+            "lat": 51.52,
+            "lon": -0.11,
+            "tz_id": "Europe/London",
+            "localtime_epoch": 1705329300,
+            "localtime": "2024-01-15 14:35"
+        },
+        "current": current
+    }))
+    .expect("sample response should deserialize")
+}
+
+// sample current weather with overridden fields
+pub fn current(overrides: Value) -> Current {
+    response(overrides).current().clone()
+}
+
+// answer a single http request with status + body, returns the base url
+pub fn serve_once(status: u16, body: &'static str) -> String {
+    serve_capture(status, body).0
+}
+
+// like serve_once, also yielding the raw request that was received
+pub fn serve_capture(status: u16, body: &'static str) -> (String, Receiver<String>) {
+    serve_sequence(vec![(status, Vec::new(), body)])
+}
+
+// canned response: status, extra headers + body
+pub type Canned = (u16, Vec<(&'static str, &'static str)>, &'static str);
+
+// answer one request per canned response, in order
+// yields the raw text of every request received
+pub fn serve_sequence(responses: Vec<Canned>) -> (String, Receiver<String>) {
+    let (sender, receiver) = mpsc::channel();
+    let listener: TcpListener = TcpListener::bind("127.0.0.1:0").expect("bind test server");
+    let port: u16 = listener.local_addr().unwrap().port();
+
+    std::thread::spawn(move || {
+        for (status, headers, body) in responses {
+            let (mut stream, _) = listener.accept().expect("accept test request");
+            let mut request: Vec<u8> = Vec::new();
+            let mut buf: [u8; 1024] = [0; 1024];
+
+            // read headers + any body announced by content-length
+            while !request_complete(&request) {
+                match stream.read(&mut buf) {
+                    Ok(0) | Err(_) => break,
+                    Ok(read) => request.extend_from_slice(&buf[..read]),
+                }
+            }
+
+            let request: String = String::from_utf8_lossy(&request).into_owned();
+            let _ = sender.send(request);
+
+            let headers: String = headers
+                .iter()
+                .map(|(name, value)| format!("{}: {}\r\n", name, value))
+                .collect();
+            let _ = write!(
+                stream,
+                "HTTP/1.1 {} Fixture\r\nContent-Type: application/json\r\nContent-Length: {}\r\n{}Connection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                headers,
+                body
+            );
+        }
+    });
+
+    (format!("http://127.0.0.1:{}/v1", port), receiver)
+}
+
+// whether request holds the full headers and the body they announce
+fn request_complete(request: &[u8]) -> bool {
+    let text: String = String::from_utf8_lossy(request).into_owned();
+    let header_end: usize = match text.find("\r\n\r\n") {
+        Some(index) => index + 4,
+        None => return false,
+    };
+    let content_length: usize = text[..header_end]
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.trim().parse().ok())
+        .unwrap_or(0);
+
+    request.len() >= header_end + content_length
+}
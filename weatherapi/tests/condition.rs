@@ -0,0 +1,53 @@
+mod common;
+
+use std::collections::HashMap;
+
+use serde_json::json;
+use weatherapi::{ConditionRenderer, Current};
+
+#[test]
+fn overridden_code_uses_custom_text() {
+    let renderer: ConditionRenderer =
+        ConditionRenderer::new(HashMap::from([(1003, "Bit cloudy".to_string())]));
+
+    assert_eq!(
+        renderer.render(common::current(json!({})).condition()),
+        "Bit cloudy"
+    );
+}
+
+#[test]
+fn unknown_code_falls_back_to_api_text() {
+    let renderer: ConditionRenderer = ConditionRenderer::default().with_override(1000, "Sunny!");
+    let current: Current = common::current(json!({}));
+
+    assert_eq!(renderer.render(current.condition()), "Partly cloudy");
+}
+
+#[test]
+fn nerdfont_glyphs_follow_emoji_groups() {
+    assert_eq!(weatherapi::condition_nerdfont(1000, true), "\u{e30d}");
+    assert_eq!(weatherapi::condition_nerdfont(1000, false), "\u{e32b}");
+    // 1183 light rain groups with the other rain codes
+    assert_eq!(
+        weatherapi::condition_nerdfont(1183, true),
+        weatherapi::condition_nerdfont(1195, false)
+    );
+    // unknown codes fall back to a thermometer
+    assert_eq!(weatherapi::condition_nerdfont(42, true), "\u{e350}");
+}
+
+#[test]
+fn partly_cloudy_has_a_night_glyph() {
+    assert_eq!(weatherapi::condition_emoji(1003, true), "⛅");
+    assert_eq!(weatherapi::condition_emoji(1003, false), "☁️🌙");
+    assert_ne!(
+        weatherapi::condition_emoji(1003, false),
+        weatherapi::condition_emoji(1006, false)
+    );
+    assert_eq!(weatherapi::condition_nerdfont(1003, false), "\u{e37e}");
+    assert_ne!(
+        weatherapi::condition_nerdfont(1003, false),
+        weatherapi::condition_nerdfont(1006, false)
+    );
+}
@@ -0,0 +1,49 @@
+use weatherapi::convert;
+
+// conversions are float math, compare with a small tolerance
+fn assert_close(actual: f32, expected: f32) {
+    assert!(
+        (actual - expected).abs() <= 0.01,
+        "expected {} to be close to {}",
+        actual,
+        expected
+    );
+}
+
+#[test]
+fn temperature_conversions() {
+    assert_close(convert::c_to_f(100.0), 212.0);
+    assert_close(convert::f_to_c(-40.0), -40.0);
+    assert_close(convert::c_to_k(0.0), 273.15);
+    assert_close(convert::k_to_c(0.0), -273.15);
+    assert_close(convert::f_to_k(32.0), 273.15);
+    assert_close(convert::k_to_f(373.15), 212.0);
+}
+
+#[test]
+fn speed_conversions() {
+    assert_close(convert::kph_to_mph(100.0), 62.14);
+    assert_close(convert::mph_to_kph(60.0), 96.56);
+    assert_close(convert::kph_to_ms(36.0), 10.0);
+    assert_close(convert::ms_to_kph(10.0), 36.0);
+    assert_close(convert::mph_to_ms(10.0), 4.47);
+    assert_close(convert::ms_to_mph(4.47), 10.0);
+}
+
+#[test]
+fn pressure_conversions() {
+    assert_close(convert::mb_to_inhg(1013.25), 29.92);
+    assert_close(convert::inhg_to_mb(29.92), 1013.21);
+    assert_close(convert::mb_to_atm(1013.25), 1.0);
+    assert_close(convert::atm_to_mb(1.0), 1013.25);
+}
+
+#[test]
+fn conversions_round_trip() {
+    for value in [-50.0, -3.3, 0.0, 14.0, 42.5] {
+        assert_close(convert::f_to_c(convert::c_to_f(value)), value);
+        assert_close(convert::k_to_f(convert::f_to_k(value)), value);
+        assert_close(convert::mph_to_kph(convert::kph_to_mph(value)), value);
+        assert_close(convert::inhg_to_mb(convert::mb_to_inhg(value)), value);
+    }
+}
@@ -0,0 +1,176 @@
+mod common;
+
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use weatherapi::{Current, PressureUnit, WindDirection};
+
+// assert floats are equal within tolerance
+fn assert_close(actual: f32, expected: f32, tolerance: f32) {
+    assert!(
+        (actual - expected).abs() <= tolerance,
+        "expected {} to be within {} of {}",
+        actual,
+        tolerance,
+        expected
+    );
+}
+
+#[test]
+fn dewpoint_matches_reference_values() {
+    let current = common::current(json!({ "temp_c": 20.0, "humidity": 50 }));
+    assert_close(current.dewpoint_c().unwrap(), 9.3, 0.1);
+    assert_close(current.dewpoint_f().unwrap(), 48.7, 0.2);
+
+    let current = common::current(json!({ "temp_c": 30.0, "humidity": 70 }));
+    assert_close(current.dewpoint_c().unwrap(), 23.9, 0.1);
+}
+
+#[test]
+fn dewpoint_equals_temperature_when_saturated() {
+    let current = common::current(json!({ "temp_c": 25.0, "humidity": 100 }));
+    assert_close(current.dewpoint_c().unwrap(), 25.0, 0.01);
+}
+
+#[test]
+fn dewpoint_is_none_without_humidity() {
+    let current = common::current(json!({ "temp_c": 20.0, "humidity": 0 }));
+    assert_eq!(current.dewpoint_c(), None);
+    assert_eq!(current.dewpoint_f(), None);
+}
+
+#[test]
+fn heat_index_matches_nws_chart() {
+    // 90°F at 60% humidity reads 100°F on the nws chart
+    let current = common::current(json!({ "temp_c": 32.22, "humidity": 60 }));
+    assert_close(current.heat_index_c().unwrap(), 37.6, 0.2);
+}
+
+#[test]
+fn heat_index_is_none_below_threshold() {
+    let current = common::current(json!({ "temp_c": 20.0, "humidity": 60 }));
+    assert_eq!(current.heat_index_c(), None);
+}
+
+#[test]
+fn feels_like_delta_is_signed() {
+    let current = common::current(json!({
+        "temp_c": 14.0, "feelslike_c": 11.0, "temp_f": 57.2, "feelslike_f": 51.8
+    }));
+    assert_close(current.feels_like_delta_c(), -3.0, 0.01);
+    assert_close(current.feels_like_delta_f(), -5.4, 0.01);
+
+    let current = common::current(json!({ "temp_c": 30.0, "feelslike_c": 33.5 }));
+    assert_close(current.feels_like_delta_c(), 3.5, 0.01);
+}
+
+#[test]
+fn missing_optional_fields_deserialize() {
+    let mut value: Value = serde_json::to_value(common::current(json!({}))).unwrap();
+    for field in [
+        "humidity",
+        "uv",
+        "gust_kph",
+        "gust_mph",
+        "is_day",
+        "last_updated",
+    ] {
+        value.as_object_mut().unwrap().remove(field);
+    }
+
+    let current: Current = serde_json::from_value(value).unwrap();
+    assert_eq!(current.humidity(), None);
+    assert_eq!(current.uv_risk(), None);
+    assert_eq!(current.gust_ms(), None);
+    assert_eq!(current.dewpoint_c(), None);
+    assert!(current.is_day());
+}
+
+#[test]
+fn wind_chill_matches_nws_chart() {
+    // 0°F with 15 mph wind reads -19°F on the nws chart
+    let current = common::current(json!({ "temp_c": -17.78, "wind_kph": 24.14 }));
+    assert_close(current.wind_chill_c().unwrap(), -28.3, 0.4);
+
+    // 20°F with 10 mph wind reads 9°F
+    let current = common::current(json!({ "temp_c": -6.67, "wind_kph": 16.09 }));
+    assert_close(current.wind_chill_c().unwrap(), -12.8, 0.4);
+
+    // -10°F with 30 mph wind reads -39°F
+    let current = common::current(json!({ "temp_c": -23.33, "wind_kph": 48.28 }));
+    assert_close(current.wind_chill_c().unwrap(), -39.4, 0.4);
+}
+
+#[test]
+fn wind_chill_is_none_outside_valid_range() {
+    let current = common::current(json!({ "temp_c": 15.0, "wind_kph": 20.0 }));
+    assert_eq!(current.wind_chill_c(), None);
+
+    let current = common::current(json!({ "temp_c": 0.0, "wind_kph": 3.0 }));
+    assert_eq!(current.wind_chill_c(), None);
+}
+
+#[test]
+fn pressure_converts_between_units() {
+    let current = common::current(json!({ "pressure_mb": 1013.25 }));
+
+    assert_close(current.pressure(PressureUnit::Hpa), 1013.25, 0.001);
+    assert_close(current.pressure(PressureUnit::Mb), 1013.25, 0.001);
+    assert_close(current.pressure(PressureUnit::InHg), 29.92, 0.01);
+    assert_close(current.pressure(PressureUnit::Atm), 1.0, 0.0001);
+}
+
+#[test]
+fn wind_direction_parses_compass_points() {
+    assert_eq!(
+        common::current(json!({ "wind_dir": "NW" })).wind_direction(),
+        Some(WindDirection::Nw)
+    );
+    assert_eq!(WindDirection::try_from("nnw").unwrap(), WindDirection::Nnw);
+    assert_eq!(WindDirection::Ese.bearing(), 112.5);
+    assert_eq!(WindDirection::Ssw.to_string(), "SSW");
+    assert!(WindDirection::try_from("north").is_err());
+}
+
+#[test]
+fn age_tracks_observation_time() {
+    let now: u64 = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let current = common::current(json!({ "last_updated_epoch": now - 20 * 60 }));
+
+    assert!(current.age().unwrap() >= Duration::from_secs(20 * 60));
+    assert!(current.is_stale(Duration::from_secs(15 * 60)));
+    assert!(!current.is_stale(Duration::from_secs(60 * 60)));
+}
+
+#[test]
+fn observation_in_the_future_has_no_age() {
+    let now: u64 = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let current = common::current(json!({ "last_updated_epoch": now + 60 * 60 }));
+
+    assert_eq!(current.age(), Some(Duration::ZERO));
+}
+
+#[test]
+fn missing_observation_time_has_unknown_age() {
+    let missing = common::current(json!({ "last_updated_epoch": null }));
+    let zero = common::current(json!({ "last_updated_epoch": 0 }));
+
+    assert_eq!(missing.age(), None);
+    assert_eq!(zero.age(), None);
+    assert!(!missing.is_stale(Duration::ZERO));
+}
+
+#[test]
+fn degree_to_compass_rounds_and_wraps() {
+    assert_eq!(weatherapi::degree_to_compass(0.0), "N");
+    assert_eq!(weatherapi::degree_to_compass(320.0), "NW");
+    assert_eq!(weatherapi::degree_to_compass(355.0), "N");
+    assert_eq!(weatherapi::degree_to_compass(-10.0), "N");
+    assert_eq!(weatherapi::degree_to_compass(f32::NAN), "N");
+}
@@ -0,0 +1,50 @@
+use weatherapi::{ApiErrorCode, Error, WeatherAPI};
+
+fn api_error(code: u32) -> Error {
+    Error::ApiError {
+        code: ApiErrorCode::from(code),
+        message: String::new(),
+    }
+}
+
+#[test]
+fn auth_errors_are_detected() {
+    assert!(api_error(1002).is_auth_error());
+    assert!(api_error(2006).is_auth_error());
+    assert!(api_error(2008).is_auth_error());
+    assert!(Error::UnexpectedStatus(401).is_auth_error());
+    assert!(!api_error(1006).is_auth_error());
+}
+
+#[test]
+fn not_found_is_detected() {
+    assert!(api_error(1006).is_not_found());
+    assert!(!api_error(2006).is_not_found());
+    assert!(!Error::BadRequest("Location must not be empty").is_not_found());
+}
+
+#[test]
+fn api_error_codes_round_trip() {
+    assert_eq!(ApiErrorCode::from(2007), ApiErrorCode::QuotaExceeded);
+    assert_eq!(ApiErrorCode::QuotaExceeded.code(), 2007);
+    assert_eq!(ApiErrorCode::from(1234), ApiErrorCode::Unknown(1234));
+    assert_eq!(ApiErrorCode::Unknown(1234).code(), 1234);
+    assert_eq!(
+        ApiErrorCode::KeyDisabled.to_string(),
+        "API key has been disabled"
+    );
+    assert_eq!(api_error(2006).to_string(), "API error 2006: ");
+}
+
+#[test]
+fn request_failure_shows_redacted_cause() {
+    let mut weatherapi = WeatherAPI::new("secret-key", "London");
+    // nothing listens on port 1
+    weatherapi.with_base_url("http://127.0.0.1:1");
+
+    let message: String = weatherapi.fetch().unwrap_err().to_string();
+
+    assert!(message.starts_with("Request failed: "), "{}", message);
+    assert!(message.len() > "Request failed: ".len(), "{}", message);
+    assert!(!message.contains("secret-key"), "{}", message);
+}
@@ -0,0 +1,66 @@
+#![cfg(feature = "async")]
+
+use std::future::Future;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::Thread;
+
+use weatherapi::{Error, WeatherAPI};
+
+// unparks the test thread when the future is ready to make progress
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+// minimal executor, the crate doesn't depend on an async runtime
+fn block_on<F: Future>(future: F) -> F::Output {
+    let waker: Waker = Arc::new(ThreadWaker(std::thread::current())).into();
+    let mut context: Context<'_> = Context::from_waker(&waker);
+    let mut future = std::pin::pin!(future);
+
+    loop {
+        match future.as_mut().poll(&mut context) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => std::thread::park(),
+        }
+    }
+}
+
+#[test]
+fn fetch_many_preserves_input_order() {
+    let mut weatherapi = WeatherAPI::new("key", "London");
+    // nothing listens on the discard port, so valid locations fail to connect
+    weatherapi.with_base_url("http://127.0.0.1:9/v1");
+
+    let results = block_on(weatherapi.fetch_many(&["London", " ", "Paris", "", "Berlin"], 2));
+
+    assert_eq!(results.len(), 5);
+    for (index, result) in results.iter().enumerate() {
+        match index {
+            1 | 3 => assert!(matches!(result, Err(Error::BadRequest(_)))),
+            _ => assert!(matches!(result, Err(Error::RequestFailed(_)))),
+        }
+    }
+}
+
+#[test]
+fn fetch_many_without_locations_is_empty() {
+    let weatherapi = WeatherAPI::new("key", "London");
+
+    assert!(block_on(weatherapi.fetch_many(&[], 4)).is_empty());
+}
+
+#[test]
+#[should_panic(expected = "observer failed")]
+fn panic_on_worker_thread_reaches_the_caller() {
+    let mut weatherapi = WeatherAPI::new("key", "London");
+    weatherapi.with_base_url("http://127.0.0.1:9/v1");
+    weatherapi.with_request_observer(|_| panic!("observer failed"));
+
+    // without forwarding the panic this would never resolve
+    let _ = block_on(weatherapi.fetch_async());
+}
@@ -0,0 +1,373 @@
+mod common;
+
+use weatherapi::{
+    ApiErrorCode, AqiSystem, DailyForecast, Error, ForecastResponse, Response, WeatherAPI,
+};
+
+const CURRENT: &str = include_str!("fixtures/current.json");
+const FORECAST: &str = include_str!("fixtures/forecast.json");
+const ERROR_NOT_FOUND: &str = include_str!("fixtures/error_1006.json");
+const ERROR_INVALID_KEY: &str = include_str!("fixtures/error_2006.json");
+const ERROR_NO_ACCESS: &str = include_str!("fixtures/error_2009.json");
+
+#[test]
+fn current_fixture_deserializes() {
+    let response: Response = serde_json::from_str(CURRENT).unwrap();
+    let current = response.current();
+
+    assert_eq!(response.location().name(), "London");
+    assert_eq!(response.location().localtime_epoch(), 1705329300);
+    assert_eq!(current.temp_c(), 4.0);
+    assert_eq!(current.feelslike_f(), 33.1);
+    assert_eq!(current.wind_dir(), "NNW");
+    assert_eq!(current.humidity(), Some(81.0));
+    assert_eq!(current.uv(), Some(1.0));
+    assert_eq!(current.condition().code(), 1009);
+    assert!(current.is_day());
+    assert_eq!(current.air_quality().unwrap().us_epa_index(), 1);
+    assert_eq!(current.air_quality().unwrap().pm2_5(), 8.9);
+}
+
+#[test]
+fn forecast_fixture_deserializes() {
+    let response: ForecastResponse = serde_json::from_str(FORECAST).unwrap();
+    let day = &response.forecast().days()[0];
+
+    assert_eq!(day.date(), "2024-01-15");
+    assert_eq!(day.day().maxtemp_c(), 5.1);
+    assert_eq!(day.day().daily_chance_of_rain(), 12);
+    assert_eq!(day.hour().len(), 2);
+    assert_eq!(day.hour()[1].chance_of_rain(), 64);
+    assert_eq!(
+        response.alerts().unwrap().alert()[0].headline(),
+        "Met Office yellow warning for ice"
+    );
+}
+
+#[test]
+fn not_found_error_keeps_api_code() {
+    let mut weatherapi = WeatherAPI::new("key", "Nowhere");
+    weatherapi.with_base_url(&common::serve_once(400, ERROR_NOT_FOUND));
+
+    let err: Error = weatherapi.fetch().unwrap_err();
+
+    assert!(err.is_not_found());
+    assert!(matches!(
+        err,
+        Error::ApiError {
+            code: ApiErrorCode::NoLocationFound,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn invalid_key_error_keeps_api_code() {
+    let mut weatherapi = WeatherAPI::new("key", "London");
+    weatherapi.with_base_url(&common::serve_once(401, ERROR_INVALID_KEY));
+
+    let err: Error = weatherapi.fetch().unwrap_err();
+
+    assert!(err.is_auth_error());
+    assert!(matches!(
+        err,
+        Error::ApiError {
+            code: ApiErrorCode::InvalidKey,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn location_is_percent_encoded() {
+    let (base_url, request) = common::serve_capture(200, CURRENT);
+    let mut weatherapi = WeatherAPI::new("key", "São Paulo & Co");
+    weatherapi.with_base_url(&base_url);
+
+    weatherapi.fetch().unwrap();
+    let request: String = request.recv().unwrap();
+
+    assert!(request.contains("q=S%C3%A3o+Paulo+%26+Co"), "{}", request);
+}
+
+#[test]
+fn nearest_location_returns_top_match() {
+    let (base_url, request) = common::serve_capture(
+        200,
+        r#"[
+            {"id": 2801268, "name": "London", "region": "City of London, Greater London", "country": "United Kingdom", "lat": 51.52, "lon": -0.11, "url": "london-city-of-london-greater-london-united-kingdom"},
+            {"id": 2796590, "name": "Holborn", "region": "Camden Greater London", "country": "United Kingdom", "lat": 51.52, "lon": -0.12, "url": "holborn-camden-greater-london-united-kingdom"}
+        ]"#,
+    );
+    let mut weatherapi = WeatherAPI::new("key", "London");
+    weatherapi.with_base_url(&base_url);
+
+    let nearest = weatherapi.nearest_location(51.52, -0.11).unwrap();
+
+    assert_eq!(nearest.name(), "London");
+    assert!(request.recv().unwrap().contains("q=51.52%2C-0.11"));
+}
+
+#[test]
+fn nearest_location_without_match_is_not_found() {
+    let mut weatherapi = WeatherAPI::new("key", "London");
+    weatherapi.with_base_url(&common::serve_once(200, "[]"));
+
+    assert!(weatherapi
+        .nearest_location(0.0, 0.0)
+        .unwrap_err()
+        .is_not_found());
+}
+
+#[test]
+fn next_rain_finds_first_rainy_hour() {
+    let response: ForecastResponse = serde_json::from_str(FORECAST).unwrap();
+    let forecast = response.forecast();
+
+    assert_eq!(
+        forecast.next_rain_after(1705276800),
+        Some(("2024-01-15 01:00".to_string(), 64))
+    );
+    // every hour of the fixture is over
+    assert_eq!(forecast.next_rain_after(1705284000), None);
+}
+
+#[test]
+fn alerts_without_access_are_reported_as_unavailable() {
+    let mut weatherapi = WeatherAPI::builder()
+        .api_key("key")
+        .location("London")
+        .include_alerts(true)
+        .build()
+        .unwrap();
+    weatherapi.with_base_url(&common::serve_once(403, ERROR_NO_ACCESS));
+
+    let err: Error = weatherapi.fetch_forecast(1).unwrap_err();
+
+    assert!(matches!(err, Error::FeatureNotAvailable("Alerts")));
+    assert_eq!(err.to_string(), "Alerts require a paid plan");
+}
+
+#[test]
+fn daily_forecast_skips_hours_and_extras() {
+    let (base_url, request) = common::serve_capture(200, FORECAST);
+    let mut weatherapi = WeatherAPI::builder()
+        .api_key("key")
+        .location("London")
+        .include_alerts(true)
+        .build()
+        .unwrap();
+    weatherapi.with_base_url(&base_url);
+
+    let daily: DailyForecast = weatherapi.fetch_forecast_daily(1).unwrap();
+    let full: ForecastResponse = serde_json::from_str(FORECAST).unwrap();
+
+    assert_eq!(daily.location(), full.location());
+    assert_eq!(daily.days()[0].date(), full.forecast().days()[0].date());
+    assert_eq!(daily.days()[0].day(), full.forecast().days()[0].day());
+
+    let request: String = request.recv().unwrap();
+    assert!(request.contains("aqi=no"));
+    assert!(!request.contains("alerts=yes"));
+}
+
+#[test]
+fn verify_key_accepts_valid_key() {
+    let mut weatherapi = WeatherAPI::new("key", "Nowhere");
+    weatherapi.with_base_url(&common::serve_once(200, CURRENT));
+
+    assert!(weatherapi.verify_key().is_ok());
+}
+
+#[test]
+fn verify_key_rejects_invalid_key() {
+    let mut weatherapi = WeatherAPI::new("key", "London");
+    weatherapi.with_base_url(&common::serve_once(401, ERROR_INVALID_KEY));
+
+    assert!(weatherapi.verify_key().unwrap_err().is_auth_error());
+}
+
+#[test]
+fn aqi_toggle_is_always_sent() {
+    let (base_url, request) = common::serve_capture(200, CURRENT);
+    let mut weatherapi = WeatherAPI::new("key", "London");
+    weatherapi.with_base_url(&base_url);
+    weatherapi.fetch().unwrap();
+
+    assert!(request.recv().unwrap().contains("aqi=no"));
+
+    let (base_url, request) = common::serve_capture(200, CURRENT);
+    weatherapi.with_base_url(&base_url);
+    weatherapi.with_aqi(true);
+    weatherapi.fetch().unwrap();
+
+    assert!(request.recv().unwrap().contains("aqi=yes"));
+}
+
+#[test]
+fn alerts_can_be_enabled_without_builder() {
+    let (base_url, request) = common::serve_capture(200, FORECAST);
+    let mut weatherapi = WeatherAPI::new("key", "London");
+    weatherapi.with_base_url(&base_url);
+    weatherapi.with_alerts(true);
+    weatherapi.fetch_forecast(1).unwrap();
+
+    assert!(request.recv().unwrap().contains("alerts=yes"));
+}
+
+#[test]
+fn aqi_is_only_sent_to_endpoints_that_support_it() {
+    let (base_url, request) = common::serve_capture(403, ERROR_NO_ACCESS);
+    let mut weatherapi = WeatherAPI::new("key", "London");
+    weatherapi.with_base_url(&base_url);
+    weatherapi.with_aqi(true);
+
+    let err: Error = weatherapi.fetch_marine(1).unwrap_err();
+
+    // marine needs a paid plan itself, the error must not blame air quality
+    assert!(!request.recv().unwrap().contains("aqi="));
+    assert!(matches!(
+        err,
+        Error::ApiError {
+            code: ApiErrorCode::NoAccess,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn throttled_request_waits_for_retry_after() {
+    let (base_url, _) = common::serve_sequence(vec![
+        (
+            429,
+            vec![("Retry-After", "1")],
+            r#"{"error": {"code": 9999, "message": "Too many requests"}}"#,
+        ),
+        (200, Vec::new(), CURRENT),
+    ]);
+    let mut weatherapi = WeatherAPI::new("key", "London");
+    weatherapi.with_base_url(&base_url);
+    weatherapi.with_retries(1);
+
+    let started: std::time::Instant = std::time::Instant::now();
+    let response: Response = weatherapi.fetch().unwrap();
+
+    assert_eq!(response.location().name(), "London");
+    assert!(started.elapsed() >= std::time::Duration::from_secs(1));
+}
+
+#[test]
+fn largest_temp_swing_picks_biggest_change() {
+    let mut forecast: serde_json::Value = serde_json::from_str(FORECAST).unwrap();
+    let day: serde_json::Value = forecast["forecast"]["forecastday"][0].clone();
+    let days: Vec<serde_json::Value> = [
+        ("2024-01-15", 2.9),
+        ("2024-01-16", 6.0),
+        ("2024-01-17", -6.5),
+    ]
+    .iter()
+    .map(|(date, avgtemp_c)| {
+        let mut day: serde_json::Value = day.clone();
+        day["date"] = serde_json::json!(date);
+        day["day"]["avgtemp_c"] = serde_json::json!(avgtemp_c);
+        day
+    })
+    .collect();
+    forecast["forecast"]["forecastday"] = serde_json::json!(days);
+
+    let response: ForecastResponse = serde_json::from_value(forecast).unwrap();
+    let (date, change) = response.forecast().largest_temp_swing().unwrap();
+
+    assert_eq!(date, "2024-01-17");
+    assert!((change + 12.5).abs() < 0.001);
+}
+
+#[test]
+fn largest_temp_swing_needs_two_days() {
+    let response: ForecastResponse = serde_json::from_str(FORECAST).unwrap();
+
+    assert_eq!(response.forecast().largest_temp_swing(), None);
+}
+
+#[test]
+fn bulk_entry_error_does_not_fail_other_locations() {
+    let current: serde_json::Value = serde_json::from_str(CURRENT).unwrap();
+    let body: String = serde_json::json!({ "bulk": [
+        { "query": { "custom_id": "1", "q": "Nowherexyz", "error": { "code": 1006, "message": "No matching location found." } } },
+        { "query": { "custom_id": "0", "q": "London", "location": current["location"], "current": current["current"] } }
+    ]})
+    .to_string();
+    let mut weatherapi = WeatherAPI::new("key", "London");
+    weatherapi.with_base_url(&common::serve_once(200, Box::leak(body.into_boxed_str())));
+
+    let results = weatherapi.fetch_bulk(&["London", "Nowherexyz"]).unwrap();
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].as_ref().unwrap().location().name(), "London");
+    assert!(results[1].as_ref().unwrap_err().is_not_found());
+}
+
+#[test]
+fn tagged_bulk_results_are_keyed_by_custom_id() {
+    let current: serde_json::Value = serde_json::from_str(CURRENT).unwrap();
+    let mut paris: serde_json::Value = current.clone();
+    paris["location"]["name"] = serde_json::json!("Paris");
+    let body: String = serde_json::json!({ "bulk": [
+        { "query": { "custom_id": "store-2", "q": "Paris", "location": paris["location"], "current": paris["current"] } },
+        { "query": { "custom_id": "store-3", "q": "Nowherexyz", "error": { "code": 1006, "message": "No matching location found." } } },
+        { "query": { "custom_id": "store-1", "q": "London", "location": current["location"], "current": current["current"] } }
+    ]})
+    .to_string();
+    let mut weatherapi = WeatherAPI::new("key", "London");
+    weatherapi.with_base_url(&common::serve_once(200, Box::leak(body.into_boxed_str())));
+
+    let results = weatherapi
+        .fetch_bulk_tagged(&[
+            ("store-1", "London"),
+            ("store-2", "Paris"),
+            ("store-3", "Nowherexyz"),
+        ])
+        .unwrap();
+
+    assert_eq!(results.len(), 3);
+    assert_eq!(
+        results["store-1"].as_ref().unwrap().location().name(),
+        "London"
+    );
+    assert_eq!(
+        results["store-2"].as_ref().unwrap().location().name(),
+        "Paris"
+    );
+    assert!(results["store-3"].as_ref().unwrap_err().is_not_found());
+}
+
+#[test]
+fn air_quality_index_by_system() {
+    let response: Response = serde_json::from_str(CURRENT).unwrap();
+    let air_quality = response.current().air_quality().unwrap();
+
+    assert_eq!(air_quality.index(AqiSystem::UsEpa), 1);
+    assert_eq!(air_quality.index(AqiSystem::GbDefra), 1);
+    assert_eq!(air_quality.us_epa_category(), "Good");
+    assert_eq!(
+        weatherapi::epa_category(3),
+        "Unhealthy for Sensitive Groups"
+    );
+    assert_eq!(weatherapi::epa_category(0), "Unknown");
+}
+
+#[test]
+fn shared_agent_is_used_for_requests() {
+    let (base_url, request) = common::serve_capture(200, CURRENT);
+    let agent: ureq::Agent = ureq::AgentBuilder::new()
+        .user_agent("shared-agent/1.0")
+        .build();
+    let mut weatherapi = WeatherAPI::new_with_agent("key", "London", agent);
+    weatherapi.with_base_url(&base_url);
+
+    weatherapi.fetch().unwrap();
+
+    let request: String = request.recv().unwrap();
+    assert!(request.starts_with("GET /v1/current.json"));
+    assert!(request.contains("shared-agent/1.0"));
+}
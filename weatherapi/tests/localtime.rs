@@ -0,0 +1,64 @@
+use serde_json::json;
+use weatherapi::Location;
+
+// location with the given localtime fields
+fn location(localtime: &str, localtime_epoch: i64) -> Location {
+    serde_json::from_value(json!({
+        "name": "Kathmandu",
+        "region": "",
+        "country": "Nepal",
+        "lat": 27.72,
+        "lon": 85.32,
+        "tz_id": "Asia/Kathmandu",
+        "localtime_epoch": localtime_epoch,
+        "localtime": localtime
+    }))
+    .unwrap()
+}
+
+#[test]
+fn local_datetime_parses_unpadded_hour() {
+    let local = location("2024-01-15 9:05", 1705288800)
+        .local_datetime()
+        .unwrap();
+
+    assert_eq!(
+        (
+            local.year(),
+            local.month(),
+            local.day(),
+            local.hour(),
+            local.minute()
+        ),
+        (2024, 1, 15, 9, 5)
+    );
+}
+
+#[test]
+fn local_datetime_is_none_when_malformed() {
+    for localtime in [
+        "",
+        "2024-01-15",
+        "2024-13-01 10:00",
+        "2024-01-15 24:00",
+        "soon",
+    ] {
+        assert_eq!(
+            location(localtime, 0).local_datetime(),
+            None,
+            "{}",
+            localtime
+        );
+    }
+}
+
+#[test]
+fn utc_offset_is_derived_from_epoch() {
+    // 2024-01-15 03:20 utc is 09:05 in kathmandu, utc+05:45
+    let kathmandu: Location = location("2024-01-15 9:05", 1705288800 + 30);
+    assert_eq!(kathmandu.utc_offset_secs(), Some(5 * 3600 + 45 * 60));
+
+    // 2024-01-15 14:35 utc is 06:35 in los angeles, utc-08:00
+    let los_angeles: Location = location("2024-01-15 6:35", 1705329300);
+    assert_eq!(los_angeles.utc_offset_secs(), Some(-8 * 3600));
+}
@@ -0,0 +1,34 @@
+use serde_json::json;
+use weatherapi::Marine;
+
+#[test]
+fn marine_response_parses_tides_and_swell() {
+    let marine: Marine = serde_json::from_value(json!({
+        "location": {
+            "name": "Brighton", "region": "East Sussex", "country": "United Kingdom",
+            "lat": 50.83, "lon": -0.15, "tz_id": "Europe/London",
+            "localtime_epoch": 1705329000, "localtime": "2024-01-15 14:30"
+        },
+        "forecast": { "forecastday": [{
+            "date": "2024-01-15",
+            "day": { "maxtemp_c": 8.0, "tides": [{ "tide": [
+                { "tide_time": "2024-01-15 04:49", "tide_height_mt": "5.92", "tide_type": "HIGH" },
+                { "tide_time": "2024-01-15 11:02", "tide_height_mt": "0.87", "tide_type": "LOW" }
+            ]}]},
+            "hour": [{
+                "time": "2024-01-15 00:00", "temp_c": 7.1, "temp_f": 44.8,
+                "condition": { "text": "Clear", "icon": "", "code": 1000 },
+                "swell_ht_mt": 0.6, "sig_ht_mt": 0.9, "swell_period_secs": 7.5
+            }]
+        }]}
+    }))
+    .unwrap();
+
+    let day = &marine.forecast().days()[0];
+    let heights: Vec<f32> = day.tides().map(|tide| tide.tide_height_mt()).collect();
+
+    assert_eq!(heights, vec![5.92, 0.87]);
+    assert_eq!(day.tides().next().unwrap().tide_type(), "HIGH");
+    assert_eq!(day.hour()[0].sig_ht_mt(), 0.9);
+    assert_eq!(day.hour()[0].swell_ht_mt(), 0.6);
+}
@@ -0,0 +1,72 @@
+mod common;
+
+use weatherapi::{Error, Response, WeatherAPI};
+
+const CURRENT: &str = include_str!("fixtures/current.json");
+
+#[test]
+fn exhausted_budget_fails_locally() {
+    let mut weatherapi = WeatherAPI::new("secret-key", "London");
+    // nothing listens on the discard port, so the first request fails to connect
+    weatherapi.with_base_url("http://127.0.0.1:9/v1");
+    weatherapi.with_rate_limit(1).unwrap();
+
+    assert!(matches!(weatherapi.fetch(), Err(Error::RequestFailed(_))));
+    assert!(matches!(
+        weatherapi.fetch(),
+        Err(Error::BadRequest("Rate limit exceeded locally"))
+    ));
+}
+
+#[test]
+fn clones_share_budget() {
+    let mut weatherapi = WeatherAPI::new("secret-key", "London");
+    weatherapi.with_base_url("http://127.0.0.1:9/v1");
+    weatherapi.with_rate_limit(1).unwrap();
+    let clone: WeatherAPI = weatherapi.clone();
+
+    assert!(weatherapi.fetch().is_err());
+    assert!(matches!(
+        clone.fetch(),
+        Err(Error::BadRequest("Rate limit exceeded locally"))
+    ));
+}
+
+#[test]
+fn retries_spend_a_single_token() {
+    let (base_url, _) = common::serve_sequence(vec![
+        (503, Vec::new(), ""),
+        (503, Vec::new(), ""),
+        (200, Vec::new(), CURRENT),
+    ]);
+    let mut weatherapi = WeatherAPI::new("secret-key", "London");
+    weatherapi.with_base_url(&base_url);
+    weatherapi.with_retries(2);
+    weatherapi.with_rate_limit(1).unwrap();
+
+    let response: Response = weatherapi.fetch().unwrap();
+
+    assert_eq!(response.location().name(), "London");
+}
+
+#[test]
+fn zero_rate_limit_is_rejected() {
+    let mut weatherapi = WeatherAPI::new("secret-key", "London");
+
+    assert!(matches!(
+        weatherapi.with_rate_limit(0),
+        Err(Error::BadRequest(
+            "Rate limit must be at least one call per minute"
+        ))
+    ));
+    assert!(matches!(
+        WeatherAPI::builder()
+            .api_key("secret-key")
+            .location("London")
+            .rate_limit(0)
+            .build(),
+        Err(Error::BadRequest(
+            "Rate limit must be at least one call per minute"
+        ))
+    ));
+}
@@ -0,0 +1,22 @@
+use weatherapi::WeatherAPI;
+
+#[test]
+fn debug_output_hides_api_key() {
+    let weatherapi = WeatherAPI::new("secret-key", "London");
+    let debug: String = format!("{:?}", weatherapi);
+
+    assert!(!debug.contains("secret-key"));
+    assert!(debug.contains("London"));
+}
+
+#[test]
+fn request_errors_hide_api_key() {
+    let mut weatherapi = WeatherAPI::new("secret-key", "London");
+    // nothing listens on the discard port, so the request fails to connect
+    weatherapi.with_base_url("http://127.0.0.1:9/v1");
+
+    let err = weatherapi.fetch().unwrap_err();
+
+    assert!(!format!("{:?}", err).contains("secret-key"));
+    assert!(!err.to_string().contains("secret-key"));
+}
@@ -0,0 +1,59 @@
+#![cfg(feature = "schema")]
+
+mod common;
+
+use serde_json::{json, Value};
+use weatherapi::Response;
+
+// every required property of schema must be present in value, and every
+// present property must have the declared type, recursively
+fn assert_matches(schema: &Value, value: &Value, path: &str) {
+    let matches_type: bool = match schema["type"].as_str() {
+        Some("object") => value.is_object(),
+        Some("number") => value.is_number(),
+        Some("integer") => value.is_i64() || value.is_u64(),
+        Some("string") => value.is_string(),
+        _ => true,
+    };
+    assert!(
+        matches_type,
+        "{} is {} but the schema expects {}",
+        path, value, schema["type"]
+    );
+
+    for name in schema["required"].as_array().into_iter().flatten() {
+        let name: &str = name.as_str().unwrap();
+        assert!(value.get(name).is_some(), "missing {}.{}", path, name);
+    }
+
+    let (properties, fields) = match (schema["properties"].as_object(), value.as_object()) {
+        (Some(properties), Some(fields)) => (properties, fields),
+        _ => return,
+    };
+    for (name, property) in properties {
+        if let Some(field) = fields.get(name) {
+            assert_matches(property, field, &format!("{}.{}", path, name));
+        }
+    }
+}
+
+#[test]
+fn schema_matches_serialized_response() {
+    let response: Response = common::response(json!({}));
+    let value: Value = serde_json::to_value(&response).unwrap();
+
+    assert_matches(&Response::json_schema(), &value, "response");
+}
+
+#[test]
+fn schema_matches_response_without_optional_fields() {
+    let mut value: Value = serde_json::to_value(common::response(json!({}))).unwrap();
+    for field in ["humidity", "uv", "gust_kph", "precip_mm"] {
+        value["current"].as_object_mut().unwrap().remove(field);
+    }
+    let response: Response = serde_json::from_value(value).unwrap();
+    let value: Value = serde_json::to_value(&response).unwrap();
+
+    assert!(value["current"].get("humidity").is_none());
+    assert_matches(&Response::json_schema(), &value, "response");
+}
@@ -0,0 +1,13 @@
+mod common;
+
+use serde_json::json;
+
+#[test]
+fn summary_is_a_single_line() {
+    let response = common::response(json!({}));
+
+    assert_eq!(
+        response.summary(),
+        "London, United Kingdom: 14°C (feels 12.9°C), Partly cloudy, wind 11.2 km/h NW"
+    );
+}